@@ -2,6 +2,7 @@ use proc_macro2::Span;
 use std::collections::{HashMap, HashSet};
 use syn::{Attribute, Ident, Lit, LitStr, MetaNameValue};
 
+#[derive(Clone)]
 pub struct JnixAttributes {
     flags: HashSet<String>,
     key_value_pairs: HashMap<String, LitStr>,
@@ -1,9 +1,10 @@
-use crate::{JnixAttributes, TypeParameters};
-use proc_macro2::{Span, TokenStream};
+use crate::{generate_sealed_class_bodies, JnixAttributes, RenameRule, TypeParameters};
+use proc_macro2::{Span, TokenStream, TokenTree};
 use quote::quote;
 use syn::{
-    parse_str, spanned::Spanned, ExprClosure, Field, Fields, Ident, Index, LitStr, Member, Pat,
-    PatType, Token, Type,
+    parse_quote, parse_str, spanned::Spanned, ExprClosure, Field, Fields, FieldsUnnamed,
+    GenericArgument, Ident, Index, LitStr, Member, Pat, PatType, PathArguments, ReturnType, Token,
+    Type,
 };
 
 pub struct ParsedField {
@@ -35,33 +36,27 @@ impl ParsedField {
         }
     }
 
-    pub fn from_named_field(field: Field) -> Option<Self> {
-        let attributes = Self::check_skip_attribute(&field)?;
+    pub fn from_named_field(field: Field) -> Self {
+        let attributes = JnixAttributes::new(&field.attrs);
         let ident = field.ident.clone().expect("Named field with no name ident");
         let span = ident.span();
         let name = ident.to_string();
         let member = Member::Named(ident);
 
-        Some(ParsedField::new(name, field, attributes, member, span))
+        ParsedField::new(name, field, attributes, member, span)
     }
 
-    pub fn from_unnamed_field((field, index): (Field, u32)) -> Option<Self> {
-        let attributes = Self::check_skip_attribute(&field)?;
+    pub fn from_unnamed_field((field, index): (Field, u32)) -> Self {
+        let attributes = JnixAttributes::new(&field.attrs);
         let span = field.ty.span();
         let name = format!("_{}", index);
         let member = Member::Unnamed(Index { index, span });
 
-        Some(ParsedField::new(name, field, attributes, member, span))
+        ParsedField::new(name, field, attributes, member, span)
     }
 
-    fn check_skip_attribute(field: &Field) -> Option<JnixAttributes> {
-        let attributes = JnixAttributes::new(&field.attrs);
-
-        if attributes.has_flag("skip") {
-            None
-        } else {
-            Some(attributes)
-        }
+    pub fn is_skipped(&self) -> bool {
+        self.attributes.has_flag("skip")
     }
 
     pub fn get_type(&self) -> &Type {
@@ -72,10 +67,10 @@ impl ParsedField {
         Ident::new(&format!("_{}_{}", prefix, self.name), self.span)
     }
 
-    pub fn preconversion(&self) -> TokenStream {
+    pub fn preconversion(&self, type_parameters: &TypeParameters) -> TokenStream {
         let source = &self.source_binding;
 
-        match self.attributes.get_value("map") {
+        let value = match self.attributes.get_value("map") {
             Some(closure_string_literal) => {
                 let mut closure = parse_str(&closure_string_literal.value())
                     .expect("Invalid closure syntax in jnix(map = ...) attribute");
@@ -85,6 +80,524 @@ impl ParsedField {
                 quote! { (#closure)(#source) }
             }
             None => quote! { #source },
+        };
+
+        let value = if self.attributes.has_flag("array") {
+            let converted_type = self.converted_type().expect(
+                "jnix(array) requires the jnix(map = ...) closure to have an explicit return type",
+            );
+
+            array_conversion(value, &converted_type)
+        } else {
+            value
+        };
+
+        let value = match (self.path_wrapper(), self.os_str_policy()) {
+            (Some(wrapper), policy) => {
+                let policy = policy.unwrap_or_else(|| quote! { jnix::os_str::OsStrPolicy::Strict });
+
+                quote! { #wrapper::new(#value, #policy) }
+            }
+            (None, Some(policy)) => quote! { jnix::os_str::JavaString::new(#value, #policy) },
+            (None, None) => value,
+        };
+
+        let value = if self.is_char_as_string() {
+            quote! { ::std::string::ToString::to_string(&#value) }
+        } else {
+            value
+        };
+
+        let value = match self.unsigned_wrapper() {
+            Some(wrapper) => quote! { #wrapper(#value) },
+            None => value,
+        };
+
+        let value = if self.attributes.has_flag("millis") {
+            quote! { jnix::time::Millis(#value) }
+        } else {
+            value
+        };
+
+        let value = if self.is_kotlin_result() {
+            quote! { jnix::result::KotlinResult(#value) }
+        } else {
+            value
+        };
+
+        let value = match self.tuple_class_name() {
+            Some(class_name) => quote! { jnix::tuples::TupleClass::new(#class_name, #value) },
+            None => value,
+        };
+
+        let value = match self.collection_class_name() {
+            Some(class_name) => quote! {
+                jnix::collections::CustomCollection::new(#class_name, #value)
+            },
+            None => value,
+        };
+
+        let value = if self.attributes.has_flag("unmodifiable") {
+            quote! { jnix::collections::Unmodifiable(#value) }
+        } else {
+            value
+        };
+
+        if self.is_boxed(type_parameters) {
+            quote! { jnix::Boxed(#value) }
+        } else {
+            value
+        }
+    }
+
+    /// Returns the expression that converts the preconverted value into its Java representation.
+    pub fn java_conversion(
+        &self,
+        converted_binding: &Ident,
+        type_parameters: &TypeParameters,
+    ) -> TokenStream {
+        match self.result_class_name() {
+            Some(class_name) => {
+                self.sealed_result_conversion(converted_binding, &class_name, type_parameters)
+            }
+            None => quote! { jnix::TryIntoJava::try_into_java(#converted_binding, env)? },
+        }
+    }
+
+    /// Converts a `Result` into a sealed class with `Ok` and `Err` subclasses, like an enum with
+    /// two single-field variants would be.
+    fn sealed_result_conversion(
+        &self,
+        converted_binding: &Ident,
+        class_name: &str,
+        type_parameters: &TypeParameters,
+    ) -> TokenStream {
+        let result_type = self.converted_type().expect(
+            "jnix(result_class = ...) requires the jnix(map = ...) closure to have an explicit \
+             return type",
+        );
+        let (ok_type, error_type) = result_types(&result_type)
+            .expect("jnix(result_class = ...) can only be used with Result types");
+
+        let variant_names = vec![
+            Ident::new("Ok", Span::call_site()),
+            Ident::new("Err", Span::call_site()),
+        ];
+        let (variant_parameters, variant_fields): (Vec<_>, Vec<_>) = [ok_type, error_type]
+            .iter()
+            .map(|variant_type| match variant_type {
+                Type::Tuple(tuple) if tuple.elems.is_empty() => (quote! { (_) }, Fields::Unit),
+                variant_type => {
+                    let fields: FieldsUnnamed = parse_quote! { (#variant_type) };
+
+                    (quote! { (_0) }, Fields::Unnamed(fields))
+                }
+            })
+            .unzip();
+
+        let class_name_literal = LitStr::new(class_name, self.span);
+        let variant_bodies = generate_sealed_class_bodies(
+            &class_name_literal,
+            &variant_names,
+            variant_fields,
+            &[JnixAttributes::empty(), JnixAttributes::empty()],
+            RenameRule::None,
+            type_parameters,
+        );
+
+        quote! {
+            match #converted_binding {
+                #(
+                    #variant_names #variant_parameters => {
+                        #variant_bodies
+                    }
+                )*
+            }?
+        }
+    }
+
+    fn tuple_class_name(&self) -> Option<String> {
+        self.attributes
+            .get_value("tuple_class")
+            .map(|class_name| class_name.value().replace(".", "/"))
+    }
+
+    fn collection_class_name(&self) -> Option<String> {
+        self.attributes
+            .get_value("collection")
+            .map(|class_name| class_name.value().replace(".", "/"))
+    }
+
+    fn result_class_name(&self) -> Option<String> {
+        self.attributes
+            .get_value("result_class")
+            .map(|class_name| class_name.value().replace(".", "/"))
+    }
+
+    fn is_kotlin_result(&self) -> bool {
+        match self.attributes.get_value("result") {
+            Some(representation) => match representation.value().as_str() {
+                "throw" => false,
+                "kotlin" => true,
+                _ => panic!(
+                    "Invalid jnix(result = ...) representation, expected \"throw\" or \"kotlin\""
+                ),
+            },
+            None => false,
+        }
+    }
+
+    fn is_boxed(&self, type_parameters: &TypeParameters) -> bool {
+        self.attributes.has_flag("boxed") || type_parameters.is_used_in_type(self.get_type())
+    }
+
+    fn path_wrapper(&self) -> Option<TokenStream> {
+        let representation = self.attributes.get_value("path")?;
+
+        match representation.value().as_str() {
+            "file" => Some(quote! { jnix::os_str::JavaFile }),
+            "nio" => Some(quote! { jnix::os_str::JavaPath }),
+            _ => panic!("Invalid jnix(path = ...) representation, expected \"file\" or \"nio\""),
+        }
+    }
+
+    fn os_str_policy(&self) -> Option<TokenStream> {
+        let policy = self.attributes.get_value("os_str")?;
+
+        match policy.value().as_str() {
+            "strict" => Some(quote! { jnix::os_str::OsStrPolicy::Strict }),
+            "lossy" => Some(quote! { jnix::os_str::OsStrPolicy::Lossy }),
+            _ => panic!("Invalid jnix(os_str = ...) policy, expected \"strict\" or \"lossy\""),
+        }
+    }
+
+    fn is_char_as_string(&self) -> bool {
+        match self.attributes.get_value("char") {
+            Some(representation) => match representation.value().as_str() {
+                "char" => false,
+                "string" => true,
+                _ => panic!(
+                    "Invalid jnix(char = ...) representation, expected \"char\" or \"string\""
+                ),
+            },
+            None => false,
+        }
+    }
+
+    fn unsigned_wrapper(&self) -> Option<TokenStream> {
+        let policy = self.attributes.get_value("unsigned")?;
+
+        match policy.value().as_str() {
+            "reinterpret" => None,
+            "widen" => Some(quote! { jnix::unsigned::Widened }),
+            "big_integer" => Some(quote! { jnix::unsigned::BigInteger }),
+            _ => panic!(
+                "Invalid jnix(unsigned = ...) policy, expected \"reinterpret\", \"widen\" or \
+                 \"big_integer\""
+            ),
+        }
+    }
+
+    pub fn converted_from_java(
+        &self,
+        jni_class_name: &str,
+        type_name_literal: &LitStr,
+        type_parameters: &TypeParameters,
+    ) -> TokenStream {
+        let class_name = jni_class_name.replace("/", ".");
+        let (source_type, from_java_map) = self.java_source();
+        let (signature, getter_signature) = if let Some(target) =
+            self.attributes.get_value("target_class")
+        {
+            let signature = format!("L{};", target.value().replace(".", "/"));
+
+            (quote! { #signature }, quote! { concat!("()", #signature) })
+        } else if type_parameters.is_used_in_type(&source_type) {
+            (
+                quote! { "Ljava/lang/Object;" },
+                quote! { "()Ljava/lang/Object;" },
+            )
+        } else {
+            (
+                quote! { <#source_type as jnix::FromJava<'env>>::JNI_SIGNATURE },
+                quote! { jnix::const_signature::GetterSignature::<'env, #source_type>::SIGNATURE },
+            )
+        };
+
+        let java_value = if self.attributes.has_flag("field") {
+            let field_name = LitStr::new(&self.java_field_name(), self.span);
+            let error_message = LitStr::new(
+                &format!(
+                    "Failed to read {}.{} field while converting {} Java object into {} Rust type",
+                    class_name,
+                    field_name.value(),
+                    class_name,
+                    type_name_literal.value(),
+                ),
+                self.span,
+            );
+
+            quote! {{
+                let field_id = env
+                    .get_cached_field_id(#jni_class_name, #field_name, #signature)
+                    .unwrap_or_else(|error| panic!("{}: {}", #error_message, error));
+                let field_type =
+                    <jnix::jni::signature::JavaType as std::str::FromStr>::from_str(#signature)
+                        .expect("Invalid Java type signature");
+
+                env.get_field_unchecked(source, field_id, field_type)
+                    .expect(#error_message)
+            }}
+        } else {
+            let getter_name = LitStr::new(&self.java_getter_name(), self.span);
+            let fallback_getter = self.java_boolean_getter_name(&source_type).map(|name| {
+                let name = LitStr::new(&name, self.span);
+
+                quote! {
+                    .or_else(|_| env.get_cached_method_id(#jni_class_name, #name, method_signature))
+                }
+            });
+            let error_message = LitStr::new(
+                &format!(
+                    "Failed to call {}.{} method while converting {} Java object into {} Rust type",
+                    class_name,
+                    getter_name.value(),
+                    class_name,
+                    type_name_literal.value(),
+                ),
+                self.span,
+            );
+
+            quote! {{
+                let method_signature = #getter_signature;
+                let method_id = env
+                    .get_cached_method_id(#jni_class_name, #getter_name, method_signature)
+                    #fallback_getter
+                    .unwrap_or_else(|error| panic!("{}: {}", #error_message, error));
+                let return_type =
+                    <jnix::jni::signature::JavaType as std::str::FromStr>::from_str(#signature)
+                        .expect("Invalid Java type signature");
+
+                env.call_method_unchecked(source, method_id, return_type, &[])
+                    .expect(#error_message)
+            }}
+        };
+
+        let value = quote! {{
+            let java_value = #java_value;
+            let value = <#source_type as jnix::FromJava<'env>>::from_java(
+                env,
+                jnix::FromJValue::from_jvalue(java_value),
+            );
+
+            if let jnix::jni::objects::JValue::Object(object) = java_value {
+                env.delete_local_ref(object)
+                    .expect("Failed to delete local reference to converted Java object");
+            }
+
+            value
+        }};
+
+        match from_java_map {
+            Some(closure) => quote! { (#closure)(#value) },
+            None => value,
+        }
+    }
+
+    /// Returns the type to convert the Java value into, and the `jnix(from_java_map = ...)` closure
+    /// that converts it into the field type, if there is one.
+    ///
+    /// A field with a `jnix(map = ...)` closure has a different type in Java, so it can only be
+    /// converted back if the inverse closure is provided.
+    fn java_source(&self) -> (Type, Option<ExprClosure>) {
+        let closure_string_literal = match self.attributes.get_value("from_java_map") {
+            Some(closure) => closure,
+            None => {
+                assert!(
+                    self.attributes.get_value("map").is_none(),
+                    "Can't derive FromJava for field {} with a jnix(map = ...) closure without a \
+                     jnix(from_java_map = ...) closure to convert the mapped value back",
+                    self.name,
+                );
+
+                return (self.get_type().clone(), None);
+            }
+        };
+
+        let closure: ExprClosure = parse_str(&closure_string_literal.value())
+            .expect("Invalid closure syntax in jnix(from_java_map = ...) attribute");
+
+        assert!(
+            closure.inputs.len() == 1,
+            "The jnix(from_java_map = ...) closure must have exactly one parameter"
+        );
+
+        let source_type = match closure.inputs.first() {
+            Some(Pat::Type(parameter)) => parameter.ty.as_ref().clone(),
+            _ => {
+                panic!("The jnix(from_java_map = ...) closure parameter must have an explicit type")
+            }
+        };
+
+        (source_type, Some(closure))
+    }
+
+    fn java_field_name(&self) -> String {
+        match &self.member {
+            Member::Named(ident) => {
+                let camel_case_name = snake_case_to_camel_case(&ident.to_string());
+                let mut characters = camel_case_name.chars();
+
+                characters
+                    .next()
+                    .map(|first| first.to_lowercase().chain(characters).collect::<String>())
+                    .unwrap_or_default()
+            }
+            Member::Unnamed(_) => panic!("Can't read unnamed field from a Java object"),
+        }
+    }
+
+    /// Kotlin keeps the name of properties that start with `is` as the name of their getters, so an
+    /// `is_active` field is read with `isActive()` instead of `getIsActive()`.
+    fn java_getter_name(&self) -> String {
+        match &self.member {
+            Member::Named(ident) => {
+                let name = ident.to_string();
+
+                if name.starts_with("is_") && name.len() > 3 {
+                    format!("is{}", snake_case_to_camel_case(&name[3..]))
+                } else {
+                    format!("get{}", snake_case_to_camel_case(&name))
+                }
+            }
+            Member::Unnamed(index) => format!("component{}", index.index + 1),
+        }
+    }
+
+    /// Returns the `isFoo()` getter name to try if a `bool` field has no `getFoo()` getter, as is
+    /// the convention for boolean properties in Java.
+    fn java_boolean_getter_name(&self, source_type: &Type) -> Option<String> {
+        let getter_name = self.java_getter_name();
+
+        match source_type {
+            Type::Path(path) if path.path.is_ident("bool") && getter_name.starts_with("get") => {
+                Some(format!("is{}", &getter_name[3..]))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the field's JNI signature as an expression that can be evaluated at compile time, if
+    /// possible.
+    ///
+    /// This isn't possible if the converted type is unknown, which happens when a `jnix(map = ...)`
+    /// closure has no explicit return type, or if it has lifetime or type parameters.
+    pub fn constant_into_java_signature(
+        &self,
+        type_parameters: &TypeParameters,
+    ) -> Option<TokenStream> {
+        if let Some(target) = self.attributes.get_value("target_class") {
+            let signature = format!("L{};", target.value().replace(".", "/"));
+
+            return Some(quote! { #signature });
+        }
+
+        if let Some(class_name) = self.result_class_name() {
+            let signature = format!("L{};", class_name);
+
+            return Some(quote! { #signature });
+        }
+
+        if type_parameters.is_used_in_type(self.get_type()) {
+            return Some(quote! { "Ljava/lang/Object;" });
+        }
+
+        if let Some(class_name) = self.tuple_class_name() {
+            let signature = format!("L{};", class_name);
+
+            return Some(quote! { #signature });
+        }
+
+        let converted_type = self.converted_type()?;
+        let converted_type_tokens = quote! { #converted_type };
+
+        if contains_lifetime(converted_type_tokens.clone())
+            || type_parameters.are_mentioned_in(converted_type_tokens.clone())
+        {
+            return None;
+        }
+
+        let converted_type = if self.attributes.has_flag("array") {
+            array_type(&converted_type)
+        } else {
+            converted_type_tokens
+        };
+
+        let converted_type = match (self.path_wrapper(), self.os_str_policy()) {
+            (Some(wrapper), _) => quote! { #wrapper<#converted_type> },
+            (None, Some(_)) => quote! { jnix::os_str::JavaString<#converted_type> },
+            (None, None) => converted_type,
+        };
+
+        let converted_type = if self.is_char_as_string() {
+            quote! { String }
+        } else {
+            converted_type
+        };
+
+        let converted_type = match self.unsigned_wrapper() {
+            Some(wrapper) => quote! { #wrapper<#converted_type> },
+            None => quote! { #converted_type },
+        };
+
+        let converted_type = if self.attributes.has_flag("millis") {
+            quote! { jnix::time::Millis<#converted_type> }
+        } else {
+            converted_type
+        };
+
+        let converted_type = if self.is_kotlin_result() {
+            quote! { jnix::result::KotlinResult<#converted_type> }
+        } else {
+            converted_type
+        };
+
+        let converted_type = if self.collection_class_name().is_some() {
+            quote! { jnix::collections::CustomCollection<#converted_type> }
+        } else {
+            converted_type
+        };
+
+        let converted_type = if self.attributes.has_flag("unmodifiable") {
+            quote! { jnix::collections::Unmodifiable<#converted_type> }
+        } else {
+            converted_type
+        };
+
+        let converted_type = if self.is_boxed(type_parameters) {
+            quote! { jnix::Boxed<#converted_type> }
+        } else {
+            converted_type
+        };
+
+        Some(quote! {
+            <#converted_type as jnix::TryIntoJava<'static, 'static>>::JNI_SIGNATURE
+        })
+    }
+
+    /// Returns the type of the value after the `jnix(map = ...)` closure is applied, if it is known.
+    fn converted_type(&self) -> Option<Type> {
+        match self.attributes.get_value("map") {
+            Some(closure_string_literal) => {
+                let closure: ExprClosure = parse_str(&closure_string_literal.value())
+                    .expect("Invalid closure syntax in jnix(map = ...) attribute");
+
+                match closure.output {
+                    ReturnType::Type(_, output_type) => Some(*output_type),
+                    ReturnType::Default => None,
+                }
+            }
+            None => Some(self.get_type().clone()),
         }
     }
 
@@ -119,32 +632,34 @@ impl ParsedField {
 
 pub struct ParsedFields {
     fields: Vec<ParsedField>,
+    skipped_fields: Vec<ParsedField>,
 }
 
 impl ParsedFields {
     pub fn new(fields: Fields, attributes: JnixAttributes) -> Self {
+        let (fields, skipped_fields) = Self::collect_parsed_fields(fields)
+            .into_iter()
+            .partition(|field| !field.is_skipped() && !attributes.has_flag("skip_all"));
+
         ParsedFields {
-            fields: Self::collect_parsed_fields(fields, attributes),
+            fields,
+            skipped_fields,
         }
     }
 
-    fn collect_parsed_fields(fields: Fields, attributes: JnixAttributes) -> Vec<ParsedField> {
-        if attributes.has_flag("skip_all") {
-            return vec![];
-        }
-
+    fn collect_parsed_fields(fields: Fields) -> Vec<ParsedField> {
         match fields {
             Fields::Unit => vec![],
             Fields::Named(fields) => fields
                 .named
                 .into_iter()
-                .filter_map(ParsedField::from_named_field)
+                .map(ParsedField::from_named_field)
                 .collect(),
             Fields::Unnamed(fields) => fields
                 .unnamed
                 .into_iter()
                 .zip(0..)
-                .filter_map(ParsedField::from_unnamed_field)
+                .map(ParsedField::from_unnamed_field)
                 .collect(),
         }
     }
@@ -152,18 +667,12 @@ impl ParsedFields {
     pub fn generate_struct_variant_into_java(
         &self,
         jni_class_name_literal: &LitStr,
-        type_name_literal: &LitStr,
-        class_name: String,
         type_parameters: &TypeParameters,
     ) -> TokenStream {
         let source_bindings = self.source_bindings();
         let original_bindings = self.original_bindings();
-        let conversion = self.generate_into_java_conversion(
-            jni_class_name_literal,
-            type_name_literal,
-            class_name,
-            type_parameters,
-        );
+        let conversion =
+            self.generate_into_java_conversion(jni_class_name_literal, type_parameters);
 
         quote! {
             #( let #source_bindings = #original_bindings; )*
@@ -174,18 +683,12 @@ impl ParsedFields {
     pub fn generate_struct_into_java(
         &self,
         jni_class_name_literal: &LitStr,
-        type_name_literal: &LitStr,
-        class_name: String,
         type_parameters: &TypeParameters,
     ) -> TokenStream {
         let source_bindings = self.source_bindings();
         let members = self.members();
-        let conversion = self.generate_into_java_conversion(
-            jni_class_name_literal,
-            type_name_literal,
-            class_name,
-            type_parameters,
-        );
+        let conversion =
+            self.generate_into_java_conversion(jni_class_name_literal, type_parameters);
 
         quote! {
             #( let #source_bindings = self.#members; )*
@@ -193,48 +696,145 @@ impl ParsedFields {
         }
     }
 
-    fn generate_into_java_conversion(
+    pub fn generate_from_java(
         &self,
-        jni_class_name_literal: &LitStr,
+        constructor: TokenStream,
+        jni_class_name: &str,
         type_name_literal: &LitStr,
-        class_name: String,
         type_parameters: &TypeParameters,
     ) -> TokenStream {
-        let signature_bindings = self.bindings("signature").collect();
-        let final_bindings = self.bindings("final").collect();
-        let declarations = self.declarations(&signature_bindings, &final_bindings, type_parameters);
+        let source_bindings: Vec<_> = self.source_bindings().collect();
+        let members = self.members();
+        let skipped_members = self.skipped_fields.iter().map(|field| &field.member);
+        let conversions = self.fields.iter().map(|field| {
+            field.converted_from_java(jni_class_name, type_name_literal, type_parameters)
+        });
 
         quote! {
-            #( #declarations )*
+            #( let #source_bindings = #conversions; )*
 
-            let mut constructor_signature = String::with_capacity(
-                1 + #( #signature_bindings.as_bytes().len() + )* 2
-            );
+            #constructor {
+                #( #members: #source_bindings, )*
+                #( #skipped_members: Default::default(), )*
+            }
+        }
+    }
+
+    fn generate_into_java_conversion(
+        &self,
+        jni_class_name_literal: &LitStr,
+        type_parameters: &TypeParameters,
+    ) -> TokenStream {
+        let final_bindings: Vec<_> = self.bindings("final").collect();
+        let constant_signatures: Option<Vec<_>> = self
+            .fields
+            .iter()
+            .map(|field| field.constant_into_java_signature(type_parameters))
+            .collect();
+
+        let constructor_signature = match constant_signatures {
+            Some(signatures) => {
+                let declarations = self.declarations(&final_bindings, None, type_parameters);
+
+                quote! {
+                    const PARAMETER_SIGNATURES: &[&str] = &[ #( #signatures ),* ];
+                    const CONSTRUCTOR_SIGNATURE_LENGTH: usize =
+                        jnix::const_signature::constructor_signature_length(PARAMETER_SIGNATURES);
+                    const CONSTRUCTOR_SIGNATURE_BYTES: [u8; CONSTRUCTOR_SIGNATURE_LENGTH] =
+                        jnix::const_signature::build_constructor_signature(PARAMETER_SIGNATURES);
+                    const CONSTRUCTOR_SIGNATURE: &str =
+                        jnix::const_signature::signature_as_str(&CONSTRUCTOR_SIGNATURE_BYTES);
+
+                    #( #declarations )*
 
-            constructor_signature.push_str("(");
-            #( constructor_signature.push_str(#signature_bindings); )*
-            constructor_signature.push_str(")V");
+                    let constructor_signature = CONSTRUCTOR_SIGNATURE;
+                }
+            }
+            None => {
+                let signature_bindings: Vec<_> = self.bindings("signature").collect();
+                let declarations =
+                    self.declarations(&final_bindings, Some(&signature_bindings), type_parameters);
+                let mut parameter_types = Vec::new();
+                let mut type_bindings = Vec::new();
+                let signatures: Vec<_> = self
+                    .fields
+                    .iter()
+                    .zip(&signature_bindings)
+                    .map(|(field, signature_binding)| {
+                        field
+                            .constant_into_java_signature(type_parameters)
+                            .unwrap_or_else(|| {
+                                let parameter_type = Ident::new(
+                                    &format!("P{}", parameter_types.len()),
+                                    Span::call_site(),
+                                );
+                                let signature = quote! {
+                                    <#parameter_type as jnix::IntoJava<'borrow, 'env>>::JNI_SIGNATURE
+                                };
+
+                                parameter_types.push(parameter_type);
+                                type_bindings.push(signature_binding.clone());
+
+                                signature
+                            })
+                    })
+                    .collect();
+
+                quote! {
+                    #( #declarations )*
+
+                    let constructor_signature = {
+                        #[allow(dead_code)]
+                        struct ConstructorSignature<'borrow, 'env, #( #parameter_types ),*>(
+                            std::marker::PhantomData<(&'borrow (), &'env (), #( #parameter_types ),*)>,
+                        );
+
+                        impl<'borrow, 'env: 'borrow, #( #parameter_types ),*>
+                            ConstructorSignature<'borrow, 'env, #( #parameter_types ),*>
+                        where
+                            #( #parameter_types: jnix::IntoJava<'borrow, 'env>, )*
+                        {
+                            const SIGNATURE: &'static str =
+                                jnix::const_signature::SignatureBuffer::as_str(
+                                    &jnix::const_signature::generic_constructor_signature(&[
+                                        #( #signatures ),*
+                                    ]),
+                                );
+
+                            fn signature(
+                                #( _: std::marker::PhantomData<#parameter_types>, )*
+                            ) -> &'static str {
+                                Self::SIGNATURE
+                            }
+                        }
+
+                        ConstructorSignature::signature(#( #type_bindings ),*)
+                    };
+                }
+            }
+        };
+
+        quote! {
+            #constructor_signature
             log::debug!("Constructor signature: {}", constructor_signature);
 
             let parameters = [ #( jnix::AsJValue::as_jvalue(&#final_bindings) ),* ];
 
-            let class = env.get_class(#jni_class_name_literal);
-            let object = env.new_object(&class, constructor_signature, &parameters)
-                .expect(concat!("Failed to convert ",
-                    #type_name_literal,
-                    " Rust type into ",
-                    #class_name,
-                    " Java object",
-                ));
+            let class = env.try_get_class(#jni_class_name_literal)?;
+            let constructor =
+                env.get_cached_method_id(#jni_class_name_literal, "<init>", constructor_signature)?;
+            let object = env
+                .new_object_unchecked(&class, constructor, &parameters)
+                .map_err(|error| env.convert_jni_error(error))?;
 
-            env.auto_local(object)
+            Ok(env.auto_local(object))
         }
     }
 
     fn declarations<'a, 'b, 'c, 'd, 'z>(
         &'a self,
-        signature_bindings: &'b Vec<Ident>,
-        final_bindings: &'c Vec<Ident>,
+        final_bindings: &'b [Ident],
+        signature_bindings: Option<&'c [Ident]>,
         type_parameters: &'d TypeParameters,
     ) -> impl Iterator<Item = TokenStream> + 'z
     where
@@ -245,25 +845,32 @@ impl ParsedFields {
     {
         self.fields
             .iter()
-            .zip(signature_bindings.iter().zip(final_bindings.iter()))
-            .map(move |(field, (signature_binding, final_binding))| {
+            .zip(final_bindings.iter())
+            .enumerate()
+            .map(move |(index, (field, final_binding))| {
                 let converted_binding = field.binding("converted");
-                let conversion = field.preconversion();
-
-                let signature = if let Some(target) = field.attributes.get_value("target_class") {
-                    let signature = format!("L{};", target.value().replace(".", "/"));
-
-                    quote! { #signature }
-                } else if type_parameters.is_used_in_type(&field.get_type()) {
-                    quote! { "Ljava/lang/Object;" }
-                } else {
-                    quote! { #converted_binding.jni_signature() }
-                };
+                let preconversion = field.preconversion(type_parameters);
+                let conversion = field.java_conversion(&converted_binding, type_parameters);
+
+                let signature_declaration = signature_bindings
+                    .filter(|_| {
+                        field
+                            .constant_into_java_signature(type_parameters)
+                            .is_none()
+                    })
+                    .map(|signature_bindings| {
+                        let signature_binding = &signature_bindings[index];
+
+                        quote! {
+                            let #signature_binding =
+                                jnix::const_signature::type_of(&#converted_binding);
+                        }
+                    });
 
                 quote! {
-                    let #converted_binding = #conversion;
-                    let #signature_binding = #signature;
-                    let #final_binding = #converted_binding.into_java(env);
+                    let #converted_binding = #preconversion;
+                    #signature_declaration
+                    let #final_binding = #conversion;
                 }
             })
     }
@@ -286,3 +893,124 @@ impl ParsedFields {
         self.fields.iter().map(|field| &field.member)
     }
 }
+
+fn snake_case_to_camel_case(name: &str) -> String {
+    name.split('_')
+        .map(|word| {
+            let mut characters = word.chars();
+
+            characters
+                .next()
+                .map(|first| first.to_uppercase().chain(characters).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Wraps a sequence value in `jnix::Array`s, so that it and any nested sequences are converted into
+/// typed Java arrays.
+fn array_conversion(value: TokenStream, sequence_type: &Type) -> TokenStream {
+    let element_type = sequence_element_type(sequence_type)
+        .expect("jnix(array) can only be used with Vec, slice, boxed slice and array types");
+
+    if sequence_element_type(element_type).is_some() {
+        let element_conversion = array_conversion(quote! { element }, element_type);
+
+        quote! {
+            jnix::Array(
+                Vec::from(#value)
+                    .into_iter()
+                    .map(|element| #element_conversion)
+                    .collect()
+            )
+        }
+    } else {
+        quote! { jnix::Array::new(#value) }
+    }
+}
+
+fn array_type(sequence_type: &Type) -> TokenStream {
+    let element_type = sequence_element_type(sequence_type)
+        .expect("jnix(array) can only be used with Vec, slice, boxed slice and array types");
+
+    let element_type = if sequence_element_type(element_type).is_some() {
+        array_type(element_type)
+    } else {
+        quote! { #element_type }
+    };
+
+    quote! { jnix::Array<#element_type> }
+}
+
+fn sequence_element_type(sequence_type: &Type) -> Option<&Type> {
+    match sequence_type {
+        Type::Array(array) => Some(&array.elem),
+        Type::Slice(slice) => Some(&slice.elem),
+        Type::Group(group) => sequence_element_type(&group.elem),
+        Type::Paren(paren) => sequence_element_type(&paren.elem),
+        Type::Reference(reference) => match reference.elem.as_ref() {
+            Type::Slice(slice) => Some(&slice.elem),
+            _ => None,
+        },
+        Type::Path(path) => {
+            let segment = path.path.segments.last()?;
+            let argument = match &segment.arguments {
+                PathArguments::AngleBracketed(arguments) if arguments.args.len() == 1 => {
+                    match arguments.args.first()? {
+                        GenericArgument::Type(argument) => argument,
+                        _ => return None,
+                    }
+                }
+                _ => return None,
+            };
+
+            if segment.ident == "Vec" {
+                Some(argument)
+            } else if segment.ident == "Box" {
+                match argument {
+                    Type::Slice(slice) => Some(&slice.elem),
+                    _ => None,
+                }
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns the `Ok` and `Err` types of a `Result` type.
+fn result_types(result_type: &Type) -> Option<(&Type, &Type)> {
+    match result_type {
+        Type::Group(group) => result_types(&group.elem),
+        Type::Paren(paren) => result_types(&paren.elem),
+        Type::Path(path) => {
+            let segment = path.path.segments.last()?;
+
+            if segment.ident != "Result" {
+                return None;
+            }
+
+            match &segment.arguments {
+                PathArguments::AngleBracketed(arguments) if arguments.args.len() == 2 => {
+                    match (arguments.args.first()?, arguments.args.last()?) {
+                        (GenericArgument::Type(ok_type), GenericArgument::Type(error_type)) => {
+                            Some((ok_type, error_type))
+                        }
+                        _ => None,
+                    }
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn contains_lifetime(tokens: TokenStream) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Punct(punctuation) => punctuation.as_char() == '\'',
+        TokenTree::Group(group) => contains_lifetime(group.stream()),
+        TokenTree::Ident(_) | TokenTree::Literal(_) => false,
+    })
+}
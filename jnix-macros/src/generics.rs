@@ -1,24 +1,67 @@
-use proc_macro2::{Span, TokenStream};
+use proc_macro2::{Span, TokenStream, TokenTree};
 use quote::quote;
-use std::{collections::HashSet, iter};
+use std::collections::HashSet;
 use syn::{
     parse_str, Generics, Ident, Lifetime, Path, ReturnType, Token, TraitBound, TraitBoundModifier,
     Type, TypeParamBound,
 };
 
+#[derive(Clone, Copy)]
+pub enum DerivedTrait {
+    IntoJava,
+    FromJava,
+}
+
+impl DerivedTrait {
+    fn lifetimes(self) -> Vec<TokenStream> {
+        match self {
+            DerivedTrait::IntoJava => vec![quote! { 'borrow }, quote! { 'env }],
+            DerivedTrait::FromJava => vec![quote! { 'env }],
+        }
+    }
+
+    fn lifetime_constraints(self) -> Vec<TokenStream> {
+        match self {
+            DerivedTrait::IntoJava => vec![quote! { 'env: 'borrow }],
+            DerivedTrait::FromJava => vec![],
+        }
+    }
+
+    fn type_predicates(self, type_parameter: &Ident) -> Vec<TokenStream> {
+        match self {
+            DerivedTrait::IntoJava => vec![quote! {
+                <#type_parameter as jnix::TryIntoJava<'borrow, 'env>>::JavaType:
+                    jnix::Boxable<'borrow, 'env>
+            }],
+            DerivedTrait::FromJava => vec![],
+        }
+    }
+
+    fn type_constraint(self) -> &'static str {
+        match self {
+            DerivedTrait::IntoJava => "jnix::TryIntoJava<'borrow, 'env>",
+            DerivedTrait::FromJava => {
+                "jnix::FromJava<'env, JavaType = jnix::jni::objects::JObject<'env>>"
+            }
+        }
+    }
+}
+
 pub struct ParsedGenerics {
+    derived_trait: DerivedTrait,
     type_parameters: Vec<Ident>,
     parameters: Vec<TokenStream>,
     constraints: Vec<TokenStream>,
 }
 
 impl ParsedGenerics {
-    pub fn new(generics: &Generics) -> Self {
+    pub fn new(generics: &Generics, derived_trait: DerivedTrait) -> Self {
         let (lifetimes, types) = Self::collect_generic_definitions(generics);
         let parameters = Self::collect_generic_params(&lifetimes, &types);
-        let constraints = Self::collect_constraints(generics);
+        let constraints = Self::collect_constraints(generics, derived_trait);
 
         ParsedGenerics {
+            derived_trait,
             type_parameters: types,
             parameters,
             constraints,
@@ -39,16 +82,16 @@ impl ParsedGenerics {
         (lifetimes, types)
     }
 
-    fn collect_generic_params(lifetimes: &Vec<Lifetime>, types: &Vec<Ident>) -> Vec<TokenStream> {
+    fn collect_generic_params(lifetimes: &[Lifetime], types: &[Ident]) -> Vec<TokenStream> {
         let lifetimes = lifetimes.iter().map(|lifetime| quote! { #lifetime });
         let types = types.iter().map(|type_param| quote! { #type_param });
 
         lifetimes.chain(types).collect()
     }
 
-    fn collect_constraints(generics: &Generics) -> Vec<TokenStream> {
-        let extra_type_constraint = Self::create_extra_type_constraint();
-        let extra_lifetime_constraints = iter::once(quote! { 'env: 'borrow });
+    fn collect_constraints(generics: &Generics, derived_trait: DerivedTrait) -> Vec<TokenStream> {
+        let extra_type_constraint = Self::create_extra_type_constraint(derived_trait);
+        let extra_lifetime_constraints = derived_trait.lifetime_constraints();
 
         let lifetime_constraints = generics
             .lifetimes()
@@ -65,18 +108,23 @@ impl ParsedGenerics {
             quote! { #type_param }
         });
 
+        let type_predicates = generics
+            .type_params()
+            .flat_map(|type_param| derived_trait.type_predicates(&type_param.ident));
+
         lifetime_constraints
             .chain(extra_lifetime_constraints)
             .chain(type_constraints)
+            .chain(type_predicates)
             .collect()
     }
 
-    fn create_extra_type_constraint() -> TypeParamBound {
+    fn create_extra_type_constraint(derived_trait: DerivedTrait) -> TypeParamBound {
         TypeParamBound::Trait(TraitBound {
             paren_token: None,
             modifier: TraitBoundModifier::None,
             lifetimes: None,
-            path: parse_str("jnix::IntoJava<'borrow, 'env>")
+            path: parse_str(derived_trait.type_constraint())
                 .expect("Invalid syntax in hardcoded string"),
         })
     }
@@ -92,14 +140,16 @@ impl ParsedGenerics {
     }
 
     pub fn impl_generics(&self) -> TokenStream {
-        let trait_parameters = [quote! { 'borrow }, quote! { 'env }];
+        let trait_parameters = self.derived_trait.lifetimes();
         let impl_parameters = trait_parameters.iter().chain(self.parameters.iter());
 
         quote! { < #( #impl_parameters ),* > }
     }
 
     pub fn trait_generics(&self) -> TokenStream {
-        quote! { <'borrow, 'env> }
+        let trait_parameters = self.derived_trait.lifetimes();
+
+        quote! { < #( #trait_parameters ),* > }
     }
 
     pub fn type_generics(&self) -> Option<TokenStream> {
@@ -154,7 +204,7 @@ impl TypeParameters {
 
                 match &function.output {
                     ReturnType::Default => false,
-                    ReturnType::Type(_, output) => self.is_used_in_type(&output),
+                    ReturnType::Type(_, output) => self.is_used_in_type(output),
                 }
             }
 
@@ -166,6 +216,17 @@ impl TypeParameters {
         }
     }
 
+    /// Checks if any of the type parameters, or `Self`, is mentioned anywhere in the tokens.
+    ///
+    /// Types that mention them can't be used in constant items nested in the generated code.
+    pub fn are_mentioned_in(&self, tokens: TokenStream) -> bool {
+        tokens.into_iter().any(|token| match token {
+            TokenTree::Ident(ident) => ident == "Self" || self.params.contains(&ident.to_string()),
+            TokenTree::Group(group) => self.are_mentioned_in(group.stream()),
+            TokenTree::Punct(_) | TokenTree::Literal(_) => false,
+        })
+    }
+
     fn contains_path(&self, path: &Path) -> bool {
         path.get_ident()
             .map(|ident| self.params.contains(&ident.to_string()))
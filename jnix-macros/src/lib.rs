@@ -3,16 +3,23 @@ extern crate proc_macro;
 mod attributes;
 mod fields;
 mod generics;
+mod rename_rule;
+mod representation;
 
 use crate::{
     attributes::JnixAttributes,
     fields::ParsedFields,
-    generics::{ParsedGenerics, TypeParameters},
+    generics::{DerivedTrait, ParsedGenerics, TypeParameters},
+    rename_rule::RenameRule,
+    representation::EnumRepresentation,
 };
 use proc_macro::TokenStream;
 use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::quote;
-use syn::{parse_macro_input, Data, DeriveInput, Fields, Ident, LitStr, Variant};
+use syn::{
+    parse_macro_input, parse_quote, parse_str, Data, DeriveInput, ExprClosure, Fields, Ident,
+    LitStr, Pat, PatType, Token, Type, Variant,
+};
 
 #[proc_macro_derive(IntoJava, attributes(jnix))]
 pub fn derive_into_java(input: TokenStream) -> TokenStream {
@@ -20,14 +27,8 @@ pub fn derive_into_java(input: TokenStream) -> TokenStream {
     let attributes = JnixAttributes::new(&parsed_input.attrs);
     let type_name = parsed_input.ident;
     let type_name_literal = LitStr::new(&type_name.to_string(), Span::call_site());
-    let class_name = attributes
-        .get_value("class_name")
-        .expect("Missing Java class name")
-        .value();
-    let jni_class_name = class_name.replace(".", "/");
-    let jni_class_name_literal = LitStr::new(&jni_class_name, Span::call_site());
-
-    let parsed_generics = ParsedGenerics::new(&parsed_input.generics);
+
+    let parsed_generics = ParsedGenerics::new(&parsed_input.generics, DerivedTrait::IntoJava);
     let impl_generics = parsed_generics.impl_generics();
     let trait_generics = parsed_generics.trait_generics();
     let type_generics = parsed_generics.type_generics();
@@ -37,25 +38,54 @@ pub fn derive_into_java(input: TokenStream) -> TokenStream {
 
     let debug = attributes.has_flag("debug");
 
-    let into_java_body = generate_into_java_body(
-        &jni_class_name_literal,
-        &type_name_literal,
-        class_name,
-        attributes,
-        parsed_input.data,
-        type_parameters,
-    );
+    let (jni_signature, java_type, into_java_body) = match EnumRepresentation::new(&attributes) {
+        Some(representation) => (
+            representation.jni_signature(),
+            representation.converted_java_type(),
+            representation.generate_into_java_body(
+                &type_name,
+                parsed_input.data,
+                RenameRule::new(&attributes),
+            ),
+        ),
+        None => {
+            let class_name = attributes
+                .get_value("class_name")
+                .expect("Missing Java class name")
+                .value();
+            let jni_class_name = class_name.replace(".", "/");
+            let jni_signature = LitStr::new(&format!("L{};", jni_class_name), Span::call_site());
+            let jni_class_name_literal = LitStr::new(&jni_class_name, Span::call_site());
+
+            let into_java_body = generate_into_java_body(
+                &jni_class_name_literal,
+                class_name,
+                attributes,
+                parsed_input.data,
+                type_parameters,
+            );
+
+            (
+                jni_signature,
+                quote! { jnix::jni::objects::AutoLocal<'env, 'borrow> },
+                into_java_body,
+            )
+        }
+    };
 
     let tokens = quote! {
         #[allow(non_snake_case)]
-        impl #impl_generics jnix::IntoJava #trait_generics for #type_name #type_generics
+        impl #impl_generics jnix::TryIntoJava #trait_generics for #type_name #type_generics
         #where_clause
         {
-            const JNI_SIGNATURE: &'static str = concat!("L", #jni_class_name_literal, ";");
+            const JNI_SIGNATURE: &'static str = #jni_signature;
 
-            type JavaType = jnix::jni::objects::AutoLocal<'env, 'borrow>;
+            type JavaType = #java_type;
 
-            fn into_java(self, env: &'borrow jnix::JnixEnv<'env>) -> Self::JavaType {
+            fn try_into_java(
+                self,
+                env: &'borrow jnix::JnixEnv<'env>,
+            ) -> Result<Self::JavaType, jnix::Error> {
                 log::debug!("IntoJava for {}", #type_name_literal);
                 #into_java_body
             }
@@ -69,9 +99,156 @@ pub fn derive_into_java(input: TokenStream) -> TokenStream {
     }
 }
 
+#[proc_macro_derive(FromJava, attributes(jnix))]
+pub fn derive_from_java(input: TokenStream) -> TokenStream {
+    let parsed_input = parse_macro_input!(input as DeriveInput);
+    let attributes = JnixAttributes::new(&parsed_input.attrs);
+    let type_name = parsed_input.ident;
+    let type_name_literal = LitStr::new(&type_name.to_string(), Span::call_site());
+
+    let parsed_generics = ParsedGenerics::new(&parsed_input.generics, DerivedTrait::FromJava);
+    let impl_generics = parsed_generics.impl_generics();
+    let trait_generics = parsed_generics.trait_generics();
+    let type_generics = parsed_generics.type_generics();
+    let where_clause = parsed_generics.where_clause();
+
+    let type_parameters = parsed_generics.type_parameters();
+
+    let debug = attributes.has_flag("debug");
+
+    let (jni_signature, java_type, from_java_body) = match EnumRepresentation::new(&attributes) {
+        Some(representation) => (
+            representation.jni_signature(),
+            representation.source_java_type(),
+            representation.generate_from_java_body(
+                &type_name,
+                &type_name_literal,
+                parsed_input.data,
+                RenameRule::new(&attributes),
+            ),
+        ),
+        None => {
+            let class_name = attributes
+                .get_value("class_name")
+                .expect("Missing Java class name")
+                .value();
+            let jni_class_name = class_name.replace(".", "/");
+            let jni_signature = LitStr::new(&format!("L{};", jni_class_name), Span::call_site());
+            let jni_class_name_literal = LitStr::new(&jni_class_name, Span::call_site());
+
+            let from_java_body = generate_from_java_body(
+                &jni_class_name_literal,
+                &type_name_literal,
+                class_name,
+                attributes,
+                parsed_input.data,
+                type_parameters,
+            );
+
+            (
+                jni_signature,
+                quote! { jnix::jni::objects::JObject<'env> },
+                from_java_body,
+            )
+        }
+    };
+
+    let tokens = quote! {
+        #[allow(non_snake_case)]
+        impl #impl_generics jnix::FromJava #trait_generics for #type_name #type_generics
+        #where_clause
+        {
+            const JNI_SIGNATURE: &'static str = #jni_signature;
+
+            type JavaType = #java_type;
+
+            fn from_java(env: &jnix::JnixEnv<'env>, source: Self::JavaType) -> Self {
+                log::debug!("FromJava for {}", #type_name_literal);
+                #from_java_body
+            }
+        }
+    };
+
+    if debug {
+        panic!("{}", TokenStream::from(tokens));
+    } else {
+        TokenStream::from(tokens)
+    }
+}
+
+#[proc_macro_derive(IntoJavaException, attributes(jnix))]
+pub fn derive_into_java_exception(input: TokenStream) -> TokenStream {
+    let parsed_input = parse_macro_input!(input as DeriveInput);
+    let attributes = JnixAttributes::new(&parsed_input.attrs);
+    let type_name = parsed_input.ident;
+    let class_name = attributes.get_value("class_name");
+    let (impl_generics, type_generics, where_clause) = parsed_input.generics.split_for_impl();
+
+    let debug = attributes.has_flag("debug");
+
+    let class_name_body = match parsed_input.data {
+        Data::Enum(data) => {
+            let (variant_names, variant_class_names): (Vec<_>, Vec<_>) = data
+                .variants
+                .into_iter()
+                .map(|variant| {
+                    let variant_class_name = JnixAttributes::new(&variant.attrs)
+                        .get_value("class_name")
+                        .or_else(|| class_name.clone())
+                        .unwrap_or_else(|| {
+                            panic!("Missing Java exception class name for {}", variant.ident)
+                        });
+
+                    (variant.ident, jni_class_name_literal(&variant_class_name))
+                })
+                .unzip();
+
+            quote! {
+                match self {
+                    #(
+                        Self::#variant_names { .. } => {
+                            std::borrow::Cow::Borrowed(#variant_class_names)
+                        }
+                    )*
+                }
+            }
+        }
+        Data::Struct(_) => {
+            let class_name =
+                jni_class_name_literal(&class_name.expect("Missing Java exception class name"));
+
+            quote! { std::borrow::Cow::Borrowed(#class_name) }
+        }
+        Data::Union(_) => panic!("Can't derive IntoJavaException for unions"),
+    };
+
+    let tokens = quote! {
+        impl #impl_generics jnix::IntoJavaException for #type_name #type_generics
+        #where_clause
+        {
+            fn exception_class_name(&self) -> std::borrow::Cow<'static, str> {
+                #class_name_body
+            }
+
+            fn exception_message(&self) -> String {
+                std::string::ToString::to_string(self)
+            }
+        }
+    };
+
+    if debug {
+        panic!("{}", TokenStream::from(tokens));
+    } else {
+        TokenStream::from(tokens)
+    }
+}
+
+fn jni_class_name_literal(class_name: &LitStr) -> LitStr {
+    LitStr::new(&class_name.value().replace(".", "/"), class_name.span())
+}
+
 fn generate_into_java_body(
     jni_class_name_literal: &LitStr,
-    type_name_literal: &LitStr,
     class_name: String,
     attributes: JnixAttributes,
     data: Data,
@@ -80,33 +257,29 @@ fn generate_into_java_body(
     match data {
         Data::Enum(data) => generate_enum_into_java_body(
             jni_class_name_literal,
-            type_name_literal,
             class_name,
             data.variants.into_iter().collect(),
+            RenameRule::new(&attributes),
             type_parameters,
         ),
-        Data::Struct(data) => ParsedFields::new(data.fields, attributes).generate_struct_into_java(
-            jni_class_name_literal,
-            type_name_literal,
-            class_name,
-            &type_parameters,
-        ),
+        Data::Struct(data) => ParsedFields::new(data.fields, attributes)
+            .generate_struct_into_java(jni_class_name_literal, &type_parameters),
         Data::Union(_) => panic!("Can't derive IntoJava for unions"),
     }
 }
 
 fn generate_enum_into_java_body(
     jni_class_name_literal: &LitStr,
-    type_name_literal: &LitStr,
     class_name: String,
     variants: Vec<Variant>,
+    rename_rule: RenameRule,
     type_parameters: TypeParameters,
 ) -> TokenStream2 {
     let (variant_names, variant_parameters, variant_bodies) = generate_enum_variants(
         jni_class_name_literal,
-        type_name_literal,
         class_name,
         variants,
+        rename_rule,
         type_parameters,
     );
 
@@ -128,31 +301,34 @@ enum TargetJavaEnumType {
     SealedClass(Vec<Ident>, Vec<Fields>),
 }
 
+/// Checks if the Java type of the enum is an enum class or a sealed class.
+///
+/// Unit variants with a `jnix(object)` attribute are represented by Kotlin objects, so they can
+/// only be part of a sealed class.
 fn parse_enum_variants(variants: Vec<Variant>) -> TargetJavaEnumType {
     use TargetJavaEnumType::*;
 
-    variants
-        .into_iter()
-        .fold(Unknown, |enum_type, variant| match enum_type {
-            Unknown => match variant.fields {
-                Fields::Unit => EnumClass(vec![variant.ident]),
-                fields @ Fields::Named(_) | fields @ Fields::Unnamed(_) => {
-                    SealedClass(vec![variant.ident], vec![fields])
-                }
-            },
+    variants.into_iter().fold(Unknown, |enum_type, variant| {
+        let is_enum_constant = match variant.fields {
+            Fields::Unit => object_field_name(&JnixAttributes::new(&variant.attrs)).is_none(),
+            Fields::Named(_) | Fields::Unnamed(_) => false,
+        };
+
+        match enum_type {
+            Unknown if is_enum_constant => EnumClass(vec![variant.ident]),
+            Unknown => SealedClass(vec![variant.ident], vec![variant.fields]),
             EnumClass(mut variant_names) => {
                 variant_names.push(variant.ident);
 
-                match variant.fields {
-                    Fields::Unit => EnumClass(variant_names),
-                    fields @ Fields::Named(_) | fields @ Fields::Unnamed(_) => {
-                        let mut variant_fields = Vec::with_capacity(variant_names.len());
+                if is_enum_constant {
+                    EnumClass(variant_names)
+                } else {
+                    let mut variant_fields = Vec::with_capacity(variant_names.len());
 
-                        variant_fields.resize(variant_names.len() - 1, Fields::Unit);
-                        variant_fields.push(fields);
+                    variant_fields.resize(variant_names.len() - 1, Fields::Unit);
+                    variant_fields.push(variant.fields);
 
-                        SealedClass(variant_names, variant_fields)
-                    }
+                    SealedClass(variant_names, variant_fields)
                 }
             }
             SealedClass(mut variant_names, mut variant_fields) => {
@@ -161,16 +337,147 @@ fn parse_enum_variants(variants: Vec<Variant>) -> TargetJavaEnumType {
 
                 SealedClass(variant_names, variant_fields)
             }
-        })
+        }
+    })
+}
+
+/// Returns the name of the static field that holds the Kotlin object representing a unit variant,
+/// if the variant has a `jnix(object)` or `jnix(object = "...")` attribute.
+fn object_field_name(attributes: &JnixAttributes) -> Option<String> {
+    if attributes.has_flag("object") {
+        Some("INSTANCE".to_owned())
+    } else {
+        attributes
+            .get_value("object")
+            .map(|field_name| field_name.value())
+    }
+}
+
+fn parse_variant_attributes(variants: &[Variant]) -> Vec<JnixAttributes> {
+    variants
+        .iter()
+        .map(|variant| JnixAttributes::new(&variant.attrs))
+        .collect()
+}
+
+/// Returns the name of the Java enum constant or nested class that represents a variant.
+fn variant_java_name(
+    variant_name: &Ident,
+    attributes: &JnixAttributes,
+    rename_rule: RenameRule,
+) -> String {
+    attributes
+        .get_value("rename")
+        .map(|name| name.value())
+        .unwrap_or_else(|| rename_rule.apply(&variant_name.to_string()))
+}
+
+/// Returns the JNI name of the class that represents a variant of a sealed class.
+///
+/// The class is nested inside the sealed class, unless the variant has a `jnix(class_name = ...)`
+/// attribute.
+fn variant_jni_class_name(
+    jni_class_name: &str,
+    variant_name: &Ident,
+    attributes: &JnixAttributes,
+    rename_rule: RenameRule,
+) -> String {
+    match attributes.get_value("class_name") {
+        Some(class_name) => class_name.value().replace(".", "/"),
+        None => format!(
+            "{}${}",
+            jni_class_name,
+            variant_java_name(variant_name, attributes, rename_rule)
+        ),
+    }
+}
+
+/// Generates the conversion of a variant with a `jnix(map = ...)` attribute.
+///
+/// The closure receives the variant's fields, as a tuple if there is more than one, and the value
+/// it returns is converted instead of the variant.
+///
+/// The converted value is used wherever the enum is expected, so its `JNI_SIGNATURE` isn't checked
+/// and it must be an instance of the enum class or of the sealed class. Mapping a variant into any
+/// other type produces objects that don't match the signature declared by the enum.
+fn generate_mapped_variant_body(closure_string_literal: &LitStr, fields: &Fields) -> TokenStream2 {
+    let mut closure: ExprClosure = parse_str(&closure_string_literal.value())
+        .expect("Invalid closure syntax in jnix(map = ...) attribute");
+
+    let (names, types): (Vec<_>, Vec<_>) = match fields {
+        Fields::Unit => (vec![], vec![]),
+        Fields::Named(named_fields) => named_fields
+            .named
+            .iter()
+            .map(|field| {
+                let name = field.ident.clone().expect("Named field without a name");
+
+                (name, field.ty.clone())
+            })
+            .unzip(),
+        Fields::Unnamed(unnamed_fields) => unnamed_fields
+            .unnamed
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                let name = Ident::new(&format!("_{}", index), Span::call_site());
+
+                (name, field.ty.clone())
+            })
+            .unzip(),
+    };
+
+    let (argument, argument_type): (TokenStream2, Type) = match names.len() {
+        0 => (quote! {}, parse_quote! { () }),
+        1 => {
+            let (name, argument_type) = (&names[0], &types[0]);
+
+            (quote! { #name }, argument_type.clone())
+        }
+        _ => (
+            quote! { ( #( #names ),* ) },
+            parse_quote! { ( #( #types ),* ) },
+        ),
+    };
+
+    if let Some(input) = closure.inputs.pop() {
+        assert!(
+            closure.inputs.is_empty(),
+            "Too many parameters in jnix(map = ...) closure"
+        );
+
+        let input = match input.into_value() {
+            input @ Pat::Type(_) => input,
+            input => Pat::Type(PatType {
+                attrs: vec![],
+                pat: Box::new(input),
+                colon_token: Token![:](Span::call_site()),
+                ty: Box::new(argument_type),
+            }),
+        };
+
+        closure.inputs.push_value(input);
+    } else {
+        assert!(
+            names.is_empty(),
+            "Missing parameter in jnix(map = ...) closure"
+        );
+    }
+
+    quote! {
+        jnix::TryIntoJava::try_into_java((#closure)(#argument), env)
+    }
 }
 
 fn generate_enum_variants(
     jni_class_name_literal: &LitStr,
-    type_name_literal: &LitStr,
     class_name: String,
     variants: Vec<Variant>,
+    rename_rule: RenameRule,
     type_parameters: TypeParameters,
 ) -> (Vec<Ident>, Vec<Option<TokenStream2>>, Vec<TokenStream2>) {
+    let variant_attributes = parse_variant_attributes(&variants);
+
     match parse_enum_variants(variants) {
         TargetJavaEnumType::Unknown => {
             panic!("Can't derive IntoJava for an enum type with no variants")
@@ -179,9 +486,10 @@ fn generate_enum_variants(
             let mut parameters = Vec::with_capacity(names.len());
             let bodies = generate_enum_class_bodies(
                 jni_class_name_literal,
-                type_name_literal,
                 class_name,
                 &names,
+                &variant_attributes,
+                rename_rule,
             );
 
             parameters.resize(names.len(), None);
@@ -189,14 +497,14 @@ fn generate_enum_variants(
             (names, parameters, bodies)
         }
         TargetJavaEnumType::SealedClass(names, fields) => {
-            let parameters = generate_enum_parameters(&fields);
+            let parameters = generate_enum_parameters(&fields, &variant_attributes);
             let bodies = generate_sealed_class_bodies(
                 jni_class_name_literal,
-                type_name_literal,
-                class_name,
                 &names,
                 fields,
-                type_parameters,
+                &variant_attributes,
+                rename_rule,
+                &type_parameters,
             );
 
             (names, parameters, bodies)
@@ -204,11 +512,17 @@ fn generate_enum_variants(
     }
 }
 
-fn generate_enum_parameters(variant_fields: &Vec<Fields>) -> Vec<Option<TokenStream2>> {
+fn generate_enum_parameters(
+    variant_fields: &[Fields],
+    variant_attributes: &[JnixAttributes],
+) -> Vec<Option<TokenStream2>> {
     variant_fields
         .iter()
-        .map(|fields| match fields {
+        .zip(variant_attributes)
+        .map(|(fields, attributes)| match fields {
             Fields::Unit => None,
+            Fields::Named(_) if skips_all_fields(attributes) => Some(quote! { { .. } }),
+            Fields::Unnamed(_) if skips_all_fields(attributes) => Some(quote! { (..) }),
             Fields::Named(named_fields) => {
                 let names = named_fields
                     .named
@@ -227,67 +541,314 @@ fn generate_enum_parameters(variant_fields: &Vec<Fields>) -> Vec<Option<TokenStr
         .collect()
 }
 
+fn skips_all_fields(attributes: &JnixAttributes) -> bool {
+    attributes.has_flag("skip_all") && attributes.get_value("map").is_none()
+}
+
 fn generate_enum_class_bodies(
     jni_class_name_literal: &LitStr,
-    type_name_literal: &LitStr,
     class_name: String,
-    variant_names: &Vec<Ident>,
+    variant_names: &[Ident],
+    variant_attributes: &[JnixAttributes],
+    rename_rule: RenameRule,
 ) -> Vec<TokenStream2> {
     variant_names
         .iter()
-        .map(|variant_name_ident| {
-            let variant_name = variant_name_ident.to_string();
-            let variant_name_literal = LitStr::new(&variant_name, Span::call_site());
+        .zip(variant_attributes)
+        .map(|(variant_name_ident, attributes)| {
+            if let Some(closure) = attributes.get_value("map") {
+                return generate_mapped_variant_body(&closure, &Fields::Unit);
+            }
 
-            quote! {
-                let class = env.get_class(#jni_class_name_literal);
-                let variant = env.get_static_field(
-                    &class,
-                    #variant_name_literal,
-                    concat!("L", #jni_class_name_literal, ";"),
-                ).expect(concat!("Failed to convert ",
-                    #type_name_literal, "::", #variant_name_literal,
-                    " Rust enum variant into ",
-                    #class_name,
-                    " Java object",
-                ));
+            let variant_name = variant_java_name(variant_name_ident, attributes, rename_rule);
 
-                match variant {
-                    jnix::jni::objects::JValue::Object(object) => env.auto_local(object),
-                    _ => panic!(concat!("Conversion from ",
-                        #type_name_literal, "::", #variant_name_literal,
-                        " Rust enum variant into ",
-                        #class_name,
-                        " Java object returned an invalid result.",
-                    )),
-                }
-            }
+            generate_static_field_body(jni_class_name_literal, &class_name, &variant_name)
         })
         .collect()
 }
 
+/// Generates the conversion of a variant that is represented by a static field of a Java class, with
+/// the class as its type.
+fn generate_static_field_body(
+    jni_class_name_literal: &LitStr,
+    class_name: &str,
+    field_name: &str,
+) -> TokenStream2 {
+    let field_name_literal = LitStr::new(field_name, Span::call_site());
+
+    quote! {
+        let class = env.try_get_class(#jni_class_name_literal)?;
+        let field_id = env.get_cached_static_field_id(
+            #jni_class_name_literal,
+            #field_name_literal,
+            concat!("L", #jni_class_name_literal, ";"),
+        )?;
+        let variant = env
+            .get_static_field_unchecked(
+                &class,
+                field_id,
+                jnix::jni::signature::JavaType::Object(String::new()),
+            )
+            .map_err(|error| env.convert_jni_error(error))?;
+
+        match variant {
+            jnix::jni::objects::JValue::Object(object) => Ok(env.auto_local(object)),
+            value => Err(jnix::Error::InvalidReturnValue {
+                source: concat!(#class_name, ".", #field_name_literal).to_owned(),
+                value_type: value.type_name(),
+            }),
+        }
+    }
+}
+
 fn generate_sealed_class_bodies(
+    jni_class_name_literal: &LitStr,
+    variant_names: &[Ident],
+    variant_fields: Vec<Fields>,
+    variant_attributes: &[JnixAttributes],
+    rename_rule: RenameRule,
+    type_parameters: &TypeParameters,
+) -> Vec<TokenStream2> {
+    variant_names
+        .iter()
+        .zip(variant_fields)
+        .zip(variant_attributes)
+        .map(|((variant_name_ident, fields), attributes)| {
+            if let Some(closure) = attributes.get_value("map") {
+                return generate_mapped_variant_body(&closure, &fields);
+            }
+
+            let jni_class_name = jni_class_name_literal.value();
+            let variant_class_name = variant_jni_class_name(
+                &jni_class_name,
+                variant_name_ident,
+                attributes,
+                rename_rule,
+            );
+            let variant_class_name_literal = LitStr::new(&variant_class_name, Span::call_site());
+
+            if let Some(field_name) = object_field_name(attributes) {
+                assert!(
+                    matches!(fields, Fields::Unit),
+                    "jnix(object) can only be used on unit variants"
+                );
+
+                let class_name = variant_class_name.replace("/", ".");
+
+                return generate_static_field_body(
+                    &variant_class_name_literal,
+                    &class_name,
+                    &field_name,
+                );
+            }
+
+            ParsedFields::new(fields, attributes.clone())
+                .generate_struct_variant_into_java(&variant_class_name_literal, type_parameters)
+        })
+        .collect()
+}
+
+fn generate_from_java_body(
     jni_class_name_literal: &LitStr,
     type_name_literal: &LitStr,
     class_name: String,
-    variant_names: &Vec<Ident>,
+    attributes: JnixAttributes,
+    data: Data,
+    type_parameters: TypeParameters,
+) -> TokenStream2 {
+    match data {
+        Data::Enum(data) => generate_enum_from_java_body(
+            jni_class_name_literal,
+            type_name_literal,
+            class_name,
+            data.variants.into_iter().collect(),
+            RenameRule::new(&attributes),
+            type_parameters,
+        ),
+        Data::Struct(data) => ParsedFields::new(data.fields, attributes).generate_from_java(
+            quote! { Self },
+            &jni_class_name_literal.value(),
+            type_name_literal,
+            &type_parameters,
+        ),
+        Data::Union(_) => panic!("Can't derive FromJava for unions"),
+    }
+}
+
+fn generate_enum_from_java_body(
+    jni_class_name_literal: &LitStr,
+    type_name_literal: &LitStr,
+    class_name: String,
+    variants: Vec<Variant>,
+    rename_rule: RenameRule,
+    type_parameters: TypeParameters,
+) -> TokenStream2 {
+    let variant_attributes = parse_variant_attributes(&variants);
+
+    for (variant, attributes) in variants.iter().zip(&variant_attributes) {
+        assert!(
+            attributes.get_value("map").is_none(),
+            "Can't derive FromJava for variant {} with a jnix(map = ...) attribute",
+            variant.ident,
+        );
+    }
+
+    match parse_enum_variants(variants) {
+        TargetJavaEnumType::Unknown => {
+            panic!("Can't derive FromJava for an enum type with no variants")
+        }
+        TargetJavaEnumType::EnumClass(names) => generate_enum_class_from_java_body(
+            type_name_literal,
+            class_name,
+            names,
+            &variant_attributes,
+            rename_rule,
+        ),
+        TargetJavaEnumType::SealedClass(names, fields) => generate_sealed_class_from_java_body(
+            jni_class_name_literal,
+            type_name_literal,
+            names,
+            fields,
+            &variant_attributes,
+            rename_rule,
+            type_parameters,
+        ),
+    }
+}
+
+fn generate_enum_class_from_java_body(
+    type_name_literal: &LitStr,
+    class_name: String,
+    variant_names: Vec<Ident>,
+    variant_attributes: &[JnixAttributes],
+    rename_rule: RenameRule,
+) -> TokenStream2 {
+    let variant_name_literals =
+        variant_names
+            .iter()
+            .zip(variant_attributes)
+            .map(|(variant_name, attributes)| {
+                LitStr::new(
+                    &variant_java_name(variant_name, attributes, rename_rule),
+                    Span::call_site(),
+                )
+            });
+
+    quote! {
+        let name_method = env
+            .get_cached_method_id("java/lang/Enum", "name", "()Ljava/lang/String;")
+            .unwrap_or_else(|error| panic!(
+                concat!("Failed to get the constant name of a ",
+                    #class_name,
+                    " Java enum object while converting it into ",
+                    #type_name_literal,
+                    " Rust enum: {}",
+                ),
+                error,
+            ));
+        let constant_name = env
+            .call_method_unchecked(
+                source,
+                name_method,
+                jnix::jni::signature::JavaType::Object(String::new()),
+                &[],
+            )
+            .and_then(|constant_name| constant_name.l())
+            .expect(concat!("Failed to get the constant name of a ",
+                #class_name,
+                " Java enum object",
+            ));
+
+        let constant_name_string = <String as jnix::FromJava<'env>>::from_java(env, constant_name);
+
+        env.delete_local_ref(constant_name)
+            .expect("Failed to delete local reference to Java enum constant name");
+
+        match constant_name_string.as_str() {
+            #( #variant_name_literals => Self::#variant_names, )*
+            unknown => panic!(
+                concat!("Can't convert unknown ",
+                    #class_name,
+                    " Java enum constant {} into ",
+                    #type_name_literal,
+                    " Rust enum variant",
+                ),
+                unknown,
+            ),
+        }
+    }
+}
+
+fn generate_sealed_class_from_java_body(
+    jni_class_name_literal: &LitStr,
+    type_name_literal: &LitStr,
+    variant_names: Vec<Ident>,
     variant_fields: Vec<Fields>,
+    variant_attributes: &[JnixAttributes],
+    rename_rule: RenameRule,
     type_parameters: TypeParameters,
-) -> Vec<TokenStream2> {
-    variant_names
+) -> TokenStream2 {
+    let class_name = jni_class_name_literal.value().replace("/", ".");
+    let (variant_class_name_literals, variant_bodies): (Vec<_>, Vec<_>) = variant_names
         .iter()
-        .zip(variant_fields.into_iter())
-        .map(|(variant_name_ident, fields)| {
+        .zip(variant_fields)
+        .zip(variant_attributes)
+        .map(|((variant_name_ident, fields), attributes)| {
             let jni_class_name = jni_class_name_literal.value();
-            let variant_class_name = format!("{}${}", jni_class_name, variant_name_ident);
+            let variant_class_name = variant_jni_class_name(
+                &jni_class_name,
+                variant_name_ident,
+                attributes,
+                rename_rule,
+            );
             let variant_class_name_literal = LitStr::new(&variant_class_name, Span::call_site());
 
-            ParsedFields::new(fields, JnixAttributes::empty()).generate_struct_variant_into_java(
-                &variant_class_name_literal,
-                &type_name_literal,
-                class_name.clone(),
+            let body = ParsedFields::new(fields, attributes.clone()).generate_from_java(
+                quote! { Self::#variant_name_ident },
+                &variant_class_name,
+                type_name_literal,
                 &type_parameters,
-            )
+            );
+
+            (variant_class_name_literal, body)
         })
-        .collect()
+        .unzip();
+
+    quote! {
+        #(
+            let variant_class = env
+                .try_get_class(#variant_class_name_literals)
+                .unwrap_or_else(|error| panic!(
+                    concat!("Failed to load ",
+                        #variant_class_name_literals,
+                        " Java class while converting ",
+                        #class_name,
+                        " Java object into ",
+                        #type_name_literal,
+                        " Rust enum: {}",
+                    ),
+                    error,
+                ));
+            let is_variant = env.is_instance_of(source, &variant_class).expect(concat!(
+                "Failed to check if ",
+                #class_name,
+                " Java object is an instance of ",
+                #variant_class_name_literals,
+                " while converting it into ",
+                #type_name_literal,
+                " Rust enum",
+            ));
+
+            if is_variant {
+                return { #variant_bodies };
+            }
+        )*
+
+        panic!(concat!("Can't convert Java object of unknown ",
+            #class_name,
+            " subclass into ",
+            #type_name_literal,
+            " Rust enum",
+        ));
+    }
 }
@@ -0,0 +1,146 @@
+use crate::JnixAttributes;
+
+/// How to rename Rust variant names into Java names, selected with the `jnix(rename_all = ...)`
+/// container attribute.
+#[derive(Clone, Copy)]
+pub enum RenameRule {
+    None,
+    LowerCase,
+    UpperCase,
+    PascalCase,
+    CamelCase,
+    SnakeCase,
+    ScreamingSnakeCase,
+}
+
+impl RenameRule {
+    pub fn new(attributes: &JnixAttributes) -> Self {
+        let rule = match attributes.get_value("rename_all") {
+            Some(rule) => rule.value(),
+            None => return RenameRule::None,
+        };
+
+        match rule.as_str() {
+            "lowercase" => RenameRule::LowerCase,
+            "UPPERCASE" => RenameRule::UpperCase,
+            "PascalCase" => RenameRule::PascalCase,
+            "camelCase" => RenameRule::CamelCase,
+            "snake_case" => RenameRule::SnakeCase,
+            "SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnakeCase,
+            _ => panic!(
+                "Invalid jnix(rename_all = ...) rule, expected \"lowercase\", \"UPPERCASE\", \
+                 \"PascalCase\", \"camelCase\", \"snake_case\" or \"SCREAMING_SNAKE_CASE\""
+            ),
+        }
+    }
+
+    /// Renames a variant name, which is expected to be in `PascalCase`.
+    pub fn apply(self, variant_name: &str) -> String {
+        match self {
+            RenameRule::None | RenameRule::PascalCase => variant_name.to_owned(),
+            RenameRule::LowerCase => variant_name.to_ascii_lowercase(),
+            RenameRule::UpperCase => variant_name.to_ascii_uppercase(),
+            RenameRule::CamelCase => {
+                let mut words = split_words(variant_name).into_iter();
+                let first_word = words.next().unwrap_or_default().to_lowercase();
+
+                words.fold(first_word, |name, word| name + word)
+            }
+            RenameRule::SnakeCase => split_words(variant_name).join("_").to_lowercase(),
+            RenameRule::ScreamingSnakeCase => split_words(variant_name).join("_").to_uppercase(),
+        }
+    }
+}
+
+/// Splits a `PascalCase` name into words.
+///
+/// A new word starts at every uppercase letter that follows a lowercase letter or a digit. A run of
+/// uppercase letters is kept together as an acronym, unless its last letter is followed by more
+/// than one lowercase letter, in which case that letter starts the next word. So `HTTPServer` is
+/// split into `HTTP` and `Server`, while `IPv4` and `URLs` are kept as single words. Digits stay
+/// in the word they follow.
+fn split_words(name: &str) -> Vec<&str> {
+    let characters: Vec<(usize, char)> = name.char_indices().collect();
+    let mut words = Vec::new();
+    let mut word_start = 0;
+
+    for position in 1..characters.len() {
+        let (index, character) = characters[position];
+        let (_, previous) = characters[position - 1];
+
+        if !character.is_uppercase() {
+            continue;
+        }
+
+        let starts_word = if previous.is_uppercase() {
+            let following_lowercase = characters[position + 1..]
+                .iter()
+                .take_while(|(_, character)| character.is_lowercase())
+                .count();
+
+            following_lowercase > 1
+        } else {
+            true
+        };
+
+        if starts_word {
+            words.push(&name[word_start..index]);
+            word_start = index;
+        }
+    }
+
+    words.push(&name[word_start..]);
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{split_words, RenameRule};
+
+    #[test]
+    fn splits_words_at_uppercase_letters() {
+        assert_eq!(split_words("A"), vec!["A"]);
+        assert_eq!(split_words("Variant"), vec!["Variant"]);
+        assert_eq!(split_words("MyVariant"), vec!["My", "Variant"]);
+        assert_eq!(split_words("ABox"), vec!["A", "Box"]);
+        assert_eq!(split_words("AB"), vec!["AB"]);
+    }
+
+    #[test]
+    fn keeps_acronyms_together() {
+        assert_eq!(split_words("HTTPServer"), vec!["HTTP", "Server"]);
+        assert_eq!(split_words("UseHTTP"), vec!["Use", "HTTP"]);
+        assert_eq!(split_words("IOError"), vec!["IO", "Error"]);
+        assert_eq!(split_words("MyURLs"), vec!["My", "URLs"]);
+    }
+
+    #[test]
+    fn keeps_digits_in_the_previous_word() {
+        assert_eq!(split_words("IPv4"), vec!["IPv4"]);
+        assert_eq!(split_words("IPv4Address"), vec!["IPv4", "Address"]);
+        assert_eq!(split_words("Sha256Hash"), vec!["Sha256", "Hash"]);
+        assert_eq!(split_words("V2"), vec!["V2"]);
+    }
+
+    #[test]
+    fn applies_rename_rules() {
+        let cases = [
+            (RenameRule::None, "HTTPServer", "HTTPServer"),
+            (RenameRule::PascalCase, "HTTPServer", "HTTPServer"),
+            (RenameRule::LowerCase, "HTTPServer", "httpserver"),
+            (RenameRule::UpperCase, "HTTPServer", "HTTPSERVER"),
+            (RenameRule::CamelCase, "HTTPServer", "httpServer"),
+            (RenameRule::CamelCase, "MyVariant", "myVariant"),
+            (RenameRule::CamelCase, "IPv4", "ipv4"),
+            (RenameRule::SnakeCase, "HTTPServer", "http_server"),
+            (RenameRule::SnakeCase, "IPv4Address", "ipv4_address"),
+            (RenameRule::ScreamingSnakeCase, "HTTPServer", "HTTP_SERVER"),
+            (RenameRule::ScreamingSnakeCase, "IPv4", "IPV4"),
+            (RenameRule::ScreamingSnakeCase, "A", "A"),
+        ];
+
+        for &(rule, name, expected) in &cases {
+            assert_eq!(rule.apply(name), expected);
+        }
+    }
+}
@@ -0,0 +1,278 @@
+use crate::{variant_java_name, JnixAttributes, RenameRule};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{Data, Fields, Ident, LitInt, LitStr};
+
+/// Alternative Java representation of an enum with only unit variants, selected with the
+/// `jnix(repr = ...)` container attribute instead of a Java enum class.
+#[derive(Clone, Copy)]
+pub enum EnumRepresentation {
+    /// The index of the variant, as an `int`.
+    Ordinal,
+
+    /// The discriminant of the variant, as an `int`.
+    Discriminant,
+
+    /// The name of the variant, as a `String`.
+    Name,
+}
+
+impl EnumRepresentation {
+    pub fn new(attributes: &JnixAttributes) -> Option<Self> {
+        let representation = attributes.get_value("repr")?;
+
+        match representation.value().as_str() {
+            "ordinal" => Some(EnumRepresentation::Ordinal),
+            "discriminant" => Some(EnumRepresentation::Discriminant),
+            "name" => Some(EnumRepresentation::Name),
+            _ => panic!(
+                "Invalid jnix(repr = ...) representation, expected \"ordinal\", \"discriminant\" \
+                 or \"name\""
+            ),
+        }
+    }
+
+    pub fn jni_signature(self) -> LitStr {
+        let signature = match self {
+            EnumRepresentation::Ordinal | EnumRepresentation::Discriminant => "I",
+            EnumRepresentation::Name => "Ljava/lang/String;",
+        };
+
+        LitStr::new(signature, Span::call_site())
+    }
+
+    pub fn converted_java_type(self) -> TokenStream {
+        match self {
+            EnumRepresentation::Ordinal | EnumRepresentation::Discriminant => {
+                quote! { jnix::jni::sys::jint }
+            }
+            EnumRepresentation::Name => quote! { jnix::jni::objects::AutoLocal<'env, 'borrow> },
+        }
+    }
+
+    pub fn source_java_type(self) -> TokenStream {
+        match self {
+            EnumRepresentation::Ordinal | EnumRepresentation::Discriminant => {
+                quote! { jnix::jni::sys::jint }
+            }
+            EnumRepresentation::Name => quote! { jnix::jni::objects::JObject<'env> },
+        }
+    }
+
+    pub fn generate_into_java_body(
+        self,
+        type_name: &Ident,
+        data: Data,
+        rename_rule: RenameRule,
+    ) -> TokenStream {
+        let (variant_names, variant_attributes) = parse_unit_variants(data);
+
+        match self {
+            EnumRepresentation::Ordinal => {
+                let ordinals = ordinals(variant_names.len());
+
+                quote! {
+                    Ok(match self {
+                        #( Self::#variant_names => #ordinals, )*
+                    })
+                }
+            }
+            EnumRepresentation::Discriminant => {
+                let assertions = discriminant_assertions(type_name, &variant_names);
+
+                quote! {
+                    #assertions
+
+                    Ok(self as jnix::jni::sys::jint)
+                }
+            }
+            EnumRepresentation::Name => {
+                let java_names = java_names(&variant_names, &variant_attributes, rename_rule);
+
+                quote! {
+                    let name = match self {
+                        #( Self::#variant_names => #java_names, )*
+                    };
+
+                    jnix::TryIntoJava::try_into_java(name, env)
+                }
+            }
+        }
+    }
+
+    pub fn generate_from_java_body(
+        self,
+        type_name: &Ident,
+        type_name_literal: &LitStr,
+        data: Data,
+        rename_rule: RenameRule,
+    ) -> TokenStream {
+        let (variant_names, variant_attributes) = parse_unit_variants(data);
+
+        match self {
+            EnumRepresentation::Ordinal => {
+                let ordinals = ordinals(variant_names.len());
+
+                quote! {
+                    match source {
+                        #( #ordinals => Self::#variant_names, )*
+                        unknown => panic!(
+                            concat!("Can't convert unknown ordinal {} into ",
+                                #type_name_literal,
+                                " Rust enum variant",
+                            ),
+                            unknown,
+                        ),
+                    }
+                }
+            }
+            EnumRepresentation::Discriminant => {
+                let assertions = discriminant_assertions(type_name, &variant_names);
+
+                quote! {
+                    #assertions
+
+                    #(
+                        if source == Self::#variant_names as jnix::jni::sys::jint {
+                            return Self::#variant_names;
+                        }
+                    )*
+
+                    panic!(
+                        concat!("Can't convert unknown discriminant {} into ",
+                            #type_name_literal,
+                            " Rust enum variant",
+                        ),
+                        source,
+                    )
+                }
+            }
+            EnumRepresentation::Name => {
+                let java_names = java_names(&variant_names, &variant_attributes, rename_rule);
+
+                quote! {
+                    match <String as jnix::FromJava<'env>>::from_java(env, source).as_str() {
+                        #( #java_names => Self::#variant_names, )*
+                        unknown => panic!(
+                            concat!("Can't convert unknown name {} into ",
+                                #type_name_literal,
+                                " Rust enum variant",
+                            ),
+                            unknown,
+                        ),
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn parse_unit_variants(data: Data) -> (Vec<Ident>, Vec<JnixAttributes>) {
+    let variants = match data {
+        Data::Enum(data) => data.variants,
+        Data::Struct(_) | Data::Union(_) => panic!("jnix(repr = ...) can only be used on enums"),
+    };
+
+    variants
+        .into_iter()
+        .map(|variant| match variant.fields {
+            Fields::Unit => (variant.ident, JnixAttributes::new(&variant.attrs)),
+            Fields::Named(_) | Fields::Unnamed(_) => {
+                panic!("jnix(repr = ...) can only be used on enums with only unit variants")
+            }
+        })
+        .unzip()
+}
+
+/// Generates constant assertions that fail the build if a discriminant doesn't fit in a Java `int`.
+fn discriminant_assertions(type_name: &Ident, variant_names: &[Ident]) -> TokenStream {
+    let messages = variant_names.iter().map(|variant_name| {
+        LitStr::new(
+            &format!(
+                "The discriminant of {}::{} doesn't fit in a Java int",
+                type_name, variant_name
+            ),
+            Span::call_site(),
+        )
+    });
+
+    quote! {
+        #(
+            const _: () = assert!(
+                (#type_name::#variant_names as i128) >= (jnix::jni::sys::jint::MIN as i128)
+                    && (#type_name::#variant_names as i128) <= (jnix::jni::sys::jint::MAX as i128),
+                #messages,
+            );
+        )*
+    }
+}
+
+fn ordinals(count: usize) -> Vec<LitInt> {
+    (0..count)
+        .map(|ordinal| LitInt::new(&ordinal.to_string(), Span::call_site()))
+        .collect()
+}
+
+fn java_names(
+    variant_names: &[Ident],
+    variant_attributes: &[JnixAttributes],
+    rename_rule: RenameRule,
+) -> Vec<LitStr> {
+    variant_names
+        .iter()
+        .zip(variant_attributes)
+        .map(|(variant_name, attributes)| {
+            LitStr::new(
+                &variant_java_name(variant_name, attributes, rename_rule),
+                Span::call_site(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{java_names, parse_unit_variants};
+    use crate::{JnixAttributes, RenameRule};
+    use syn::{parse_quote, DeriveInput, LitStr};
+
+    fn test_enum() -> DeriveInput {
+        parse_quote! {
+            #[jnix(repr = "name")]
+            #[jnix(rename_all = "SCREAMING_SNAKE_CASE")]
+            enum Protocol {
+                WireGuard,
+                #[jnix(rename = "ovpn")]
+                OpenVpn,
+                HTTPProxy,
+            }
+        }
+    }
+
+    #[test]
+    fn names_are_renamed() {
+        let input = test_enum();
+        let rename_rule = RenameRule::new(&JnixAttributes::new(&input.attrs));
+        let (variant_names, variant_attributes) = parse_unit_variants(input.data);
+
+        let names: Vec<_> = java_names(&variant_names, &variant_attributes, rename_rule)
+            .iter()
+            .map(LitStr::value)
+            .collect();
+
+        assert_eq!(names, vec!["WIRE_GUARD", "ovpn", "HTTP_PROXY"]);
+    }
+
+    #[test]
+    fn names_are_kept_without_a_rename_rule() {
+        let input = test_enum();
+        let (variant_names, variant_attributes) = parse_unit_variants(input.data);
+
+        let names: Vec<_> = java_names(&variant_names, &variant_attributes, RenameRule::None)
+            .iter()
+            .map(LitStr::value)
+            .collect();
+
+        assert_eq!(names, vec!["WireGuard", "ovpn", "HTTPProxy"]);
+    }
+}
@@ -0,0 +1,37 @@
+use crate::{
+    const_signature::{array_signature, SignatureBuffer},
+    Error, JnixEnv, TryIntoJava, TypedArrayElement,
+};
+use jni::objects::AutoLocal;
+
+/// Converts a sequence of values into a typed Java array.
+///
+/// The element class of the array is taken from the elements' JNI signature, so a sequence of
+/// `String`s becomes a `String[]` and a sequence of `Box<[u8]>`s becomes a `byte[][]`. Arrays can be
+/// nested by using `Array`s as elements.
+///
+/// Unlike `Vec<T>`, which becomes a `java.util.ArrayList`, an `Array` keeps the element type in
+/// its signature. It is used by the derive macros when a field has the `#[jnix(array)]` attribute.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Array<T>(pub Vec<T>);
+
+impl<T> Array<T> {
+    pub fn new(elements: impl Into<Vec<T>>) -> Self {
+        Array(elements.into())
+    }
+}
+
+impl<'borrow, 'env, T> TryIntoJava<'borrow, 'env> for Array<T>
+where
+    'env: 'borrow,
+    T: TryIntoJava<'borrow, 'env>,
+    T::JavaType: TypedArrayElement<'borrow, 'env>,
+{
+    const JNI_SIGNATURE: &'static str = SignatureBuffer::as_str(&array_signature(T::JNI_SIGNATURE));
+
+    type JavaType = AutoLocal<'env, 'borrow>;
+
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        TypedArrayElement::new_typed_array(self.0, T::JNI_SIGNATURE, env)
+    }
+}
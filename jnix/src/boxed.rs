@@ -0,0 +1,122 @@
+use crate::{AsJValue, Error, JnixEnv, TryIntoJava};
+use jni::{
+    objects::{AutoLocal, JValue},
+    signature::JavaType,
+    sys::{jboolean, jbyte, jchar, jdouble, jfloat, jint, jlong, jshort},
+};
+
+/// Converts a value into a Java object, boxing primitive values.
+///
+/// Primitive values are converted into their wrapper classes (e.g. `java.lang.Integer`) through
+/// the `valueOf` factory methods, and values that are already Java objects are left untouched.
+/// This is needed wherever Java expects an object, like in generic classes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Boxed<T>(pub T);
+
+impl<'borrow, 'env, T> TryIntoJava<'borrow, 'env> for Boxed<T>
+where
+    'env: 'borrow,
+    T: TryIntoJava<'borrow, 'env>,
+    T::JavaType: Boxable<'borrow, 'env>,
+{
+    const JNI_SIGNATURE: &'static str =
+        match <T::JavaType as Boxable<'borrow, 'env>>::BOXED_JNI_SIGNATURE {
+            Some(boxed_signature) => boxed_signature,
+            None => T::JNI_SIGNATURE,
+        };
+
+    type JavaType = AutoLocal<'env, 'borrow>;
+
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        self.0.try_into_java(env)?.into_boxed(env)
+    }
+}
+
+/// Java values that can be represented as Java objects.
+pub trait Boxable<'borrow, 'env: 'borrow> {
+    /// The JNI signature of the wrapper class, or `None` if the value is already an object.
+    const BOXED_JNI_SIGNATURE: Option<&'static str>;
+
+    fn into_boxed(self, env: &'borrow JnixEnv<'env>) -> Result<AutoLocal<'env, 'borrow>, Error>;
+}
+
+impl<'borrow, 'env: 'borrow> Boxable<'borrow, 'env> for AutoLocal<'env, 'borrow> {
+    const BOXED_JNI_SIGNATURE: Option<&'static str> = None;
+
+    fn into_boxed(self, _: &'borrow JnixEnv<'env>) -> Result<AutoLocal<'env, 'borrow>, Error> {
+        Ok(self)
+    }
+}
+
+/// The unit value is represented by the `kotlin.Unit` singleton.
+impl<'borrow, 'env: 'borrow> Boxable<'borrow, 'env> for () {
+    const BOXED_JNI_SIGNATURE: Option<&'static str> = Some("Lkotlin/Unit;");
+
+    fn into_boxed(self, env: &'borrow JnixEnv<'env>) -> Result<AutoLocal<'env, 'borrow>, Error> {
+        let class = env.try_get_class("kotlin/Unit")?;
+        let field_id =
+            env.get_cached_static_field_id("kotlin/Unit", "INSTANCE", "Lkotlin/Unit;")?;
+        let unit = env
+            .get_static_field_unchecked(&class, field_id, JavaType::Object(String::new()))
+            .map_err(|error| env.convert_jni_error(error))?;
+
+        match unit {
+            JValue::Object(object) => Ok(env.auto_local(object)),
+            value => Err(Error::InvalidReturnValue {
+                source: "kotlin.Unit.INSTANCE".to_owned(),
+                value_type: value.type_name(),
+            }),
+        }
+    }
+}
+
+macro_rules! impl_boxable_for_primitives {
+    ( $( $primitive:ty => $class_name:expr, $signature:expr ),* $(,)* ) => {
+        $(
+            impl<'borrow, 'env: 'borrow> Boxable<'borrow, 'env> for $primitive {
+                const BOXED_JNI_SIGNATURE: Option<&'static str> =
+                    Some(concat!("L", $class_name, ";"));
+
+                fn into_boxed(
+                    self,
+                    env: &'borrow JnixEnv<'env>,
+                ) -> Result<AutoLocal<'env, 'borrow>, Error> {
+                    let class = env.try_get_class($class_name)?;
+                    let value_of = env.get_cached_static_method_id(
+                        $class_name,
+                        "valueOf",
+                        concat!("(", $signature, ")L", $class_name, ";"),
+                    )?;
+
+                    let result = env
+                        .call_static_method_unchecked(
+                            &class,
+                            value_of,
+                            JavaType::Object($class_name.to_owned()),
+                            &[self.as_jvalue()],
+                        )
+                        .map_err(|error| env.convert_jni_error(error))?;
+
+                    match result {
+                        JValue::Object(object) => Ok(env.auto_local(object)),
+                        value => Err(Error::InvalidReturnValue {
+                            source: concat!($class_name, ".valueOf").replace("/", "."),
+                            value_type: value.type_name(),
+                        }),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_boxable_for_primitives! {
+    jboolean => "java/lang/Boolean", "Z",
+    jbyte => "java/lang/Byte", "B",
+    jchar => "java/lang/Character", "C",
+    jshort => "java/lang/Short", "S",
+    jint => "java/lang/Integer", "I",
+    jlong => "java/lang/Long", "J",
+    jfloat => "java/lang/Float", "F",
+    jdouble => "java/lang/Double", "D",
+}
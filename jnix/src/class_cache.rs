@@ -0,0 +1,169 @@
+use jni::{objects::GlobalRef, sys::JNI_TRUE, JNIEnv, JavaVM};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+
+/// Caches indexed by the address of the `JavaVM` they belong to.
+pub(crate) static CLASS_CACHES: Lazy<Mutex<HashMap<usize, JavaVmCaches>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// The class caches of a single `JavaVM`, one for each class loader that classes are loaded with.
+#[derive(Default)]
+pub(crate) struct JavaVmCaches {
+    pub class_loader: Option<GlobalRef>,
+    caches: Vec<ClassCache>,
+}
+
+impl JavaVmCaches {
+    /// Returns the cache of the classes loaded with a class loader, or with the registered class
+    /// loader if none is specified.
+    ///
+    /// Class loaders are compared with `IsSameObject`, which doesn't run any Java code, so this is
+    /// safe to call while the caches are locked.
+    pub fn cache_for(&mut self, env: &JNIEnv, class_loader: Option<&GlobalRef>) -> &mut ClassCache {
+        let class_loader = class_loader.or(self.class_loader.as_ref()).cloned();
+        let position =
+            self.caches
+                .iter()
+                .position(|cache| match (&cache.class_loader, &class_loader) {
+                    (None, None) => true,
+                    (Some(cached), Some(requested)) => is_same_object(env, cached, requested),
+                    _ => false,
+                });
+
+        match position {
+            Some(position) => &mut self.caches[position],
+            None => {
+                self.caches.push(ClassCache {
+                    class_loader,
+                    ..ClassCache::default()
+                });
+
+                self.caches.last_mut().expect("Class cache was just added")
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.caches.clear();
+    }
+
+    pub fn remove_class(&mut self, class_name: &str) -> bool {
+        let mut removed = false;
+
+        for cache in &mut self.caches {
+            removed |= cache.remove_class(class_name);
+        }
+
+        removed
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) enum MemberKind {
+    Method,
+    StaticMethod,
+    Field,
+    StaticField,
+}
+
+impl MemberKind {
+    /// The name of the Java exception that is thrown when a member of this kind doesn't exist.
+    pub fn not_found_exception_name(self) -> &'static str {
+        match self {
+            MemberKind::Method | MemberKind::StaticMethod => "java.lang.NoSuchMethodError",
+            MemberKind::Field | MemberKind::StaticField => "java.lang.NoSuchFieldError",
+        }
+    }
+}
+
+/// Member IDs indexed by class name, member name and signature.
+///
+/// The IDs are stored as addresses so that they can be shared between threads. They remain valid
+/// for as long as the class isn't unloaded, which is prevented by the global reference kept in the
+/// class cache. They are removed together with the class they belong to. Members that weren't
+/// found are cached as `None`, so that the lookup isn't repeated. Lookups that fail for any other
+/// reason aren't cached.
+type MemberIdCache = HashMap<String, HashMap<String, HashMap<String, Option<usize>>>>;
+
+fn is_same_object(env: &JNIEnv, first: &GlobalRef, second: &GlobalRef) -> bool {
+    let raw_env = env.get_native_interface();
+
+    match unsafe { (**raw_env).IsSameObject } {
+        Some(is_same_object) => {
+            let result = unsafe {
+                is_same_object(
+                    raw_env,
+                    first.as_obj().into_inner(),
+                    second.as_obj().into_inner(),
+                )
+            };
+
+            result == JNI_TRUE
+        }
+        None => false,
+    }
+}
+
+/// Classes and member IDs loaded with a single class loader in a single `JavaVM`.
+///
+/// Classes loaded without a class loader are the ones found through `FindClass`.
+#[derive(Default)]
+pub(crate) struct ClassCache {
+    pub class_loader: Option<GlobalRef>,
+    pub classes: HashMap<String, GlobalRef>,
+    member_ids: HashMap<MemberKind, MemberIdCache>,
+}
+
+impl ClassCache {
+    pub fn remove_class(&mut self, class_name: &str) -> bool {
+        for classes in self.member_ids.values_mut() {
+            classes.remove(class_name);
+        }
+
+        self.classes.remove(class_name).is_some()
+    }
+
+    pub fn get_member_id(
+        &self,
+        kind: MemberKind,
+        class_name: &str,
+        member_name: &str,
+        signature: &str,
+    ) -> Option<Option<usize>> {
+        self.member_ids
+            .get(&kind)
+            .and_then(|classes| classes.get(class_name))
+            .and_then(|members| members.get(member_name))
+            .and_then(|signatures| signatures.get(signature))
+            .copied()
+    }
+
+    pub fn insert_member_id(
+        &mut self,
+        kind: MemberKind,
+        class_name: &str,
+        member_name: &str,
+        signature: &str,
+        id: Option<usize>,
+    ) {
+        self.member_ids
+            .entry(kind)
+            .or_default()
+            .entry(class_name.to_owned())
+            .or_default()
+            .entry(member_name.to_owned())
+            .or_default()
+            .insert(signature.to_owned(), id);
+    }
+}
+
+/// Drops all cached classes, member IDs and class loaders of a `JavaVM`.
+///
+/// This should be called from `JNI_OnUnload`, so that no global references are leaked when the
+/// native library is unloaded.
+pub fn on_unload(java_vm: &JavaVM) {
+    CLASS_CACHES
+        .lock()
+        .remove(&(java_vm.get_java_vm_pointer() as usize));
+}
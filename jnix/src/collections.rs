@@ -0,0 +1,379 @@
+//! Conversions of Rust collections into a chosen Java collection class.
+//!
+//! By default, each Rust collection is converted into a fixed Java collection class (e.g. `Vec<T>`
+//! becomes a `java.util.ArrayList`). The wrappers in this module select a different class or wrap
+//! the result in an unmodifiable view. They always create a Java collection with boxed elements, so
+//! even a `Vec` of primitives becomes a `java.util.List` instead of a primitive array. They are
+//! used by the derive macros when a field has the `#[jnix(collection = "...")]` or
+//! `#[jnix(unmodifiable)]` attributes.
+//!
+//! The JNI signature of both wrappers is the signature of the collection's Java interface (e.g.
+//! `java.util.List` for a `Vec<T>`), with or without the other wrapper, so the Java parameters that
+//! receive them must be declared with the interface type.
+
+use crate::{Boxable, Boxed, Error, JnixEnv, TryIntoJava};
+use jni::{
+    objects::{AutoLocal, JValue},
+    signature::{JavaType, Primitive},
+    sys::jint,
+};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+/// Rust collections and the Java interface implemented by the collections they are converted into.
+pub trait JavaCollection {
+    /// The name of the Java interface, in JNI format (e.g. `java/util/List`).
+    const INTERFACE_CLASS_NAME: &'static str;
+
+    /// The JNI signature of the Java interface.
+    const INTERFACE_SIGNATURE: &'static str;
+
+    /// The `java.util.Collections` method that returns an unmodifiable view of the interface.
+    const UNMODIFIABLE_METHOD_NAME: &'static str;
+
+    /// The JNI signature of the `java.util.Collections` method that returns an unmodifiable view of
+    /// the interface.
+    const UNMODIFIABLE_METHOD_SIGNATURE: &'static str;
+}
+
+/// Rust collections that can be converted into a Java collection implementing their interface.
+pub trait IntoJavaCollection<'borrow, 'env: 'borrow>: JavaCollection {
+    fn into_java_collection(
+        self,
+        env: &'borrow JnixEnv<'env>,
+    ) -> Result<AutoLocal<'env, 'borrow>, Error>;
+}
+
+/// Rust collections whose elements can be added to any Java collection implementing their
+/// interface.
+pub trait CollectionContents<'borrow, 'env: 'borrow>: JavaCollection {
+    /// The name of the Java class the collection is converted into by default, in JNI format.
+    const DEFAULT_CLASS_NAME: &'static str;
+
+    /// The initial capacity to create the Java collection with, if it should be preallocated.
+    fn initial_capacity(&self) -> Option<usize>;
+
+    fn add_to(
+        self,
+        collection: &AutoLocal<'env, 'borrow>,
+        env: &'borrow JnixEnv<'env>,
+    ) -> Result<(), Error>;
+}
+
+macro_rules! impl_java_collection {
+    ( $( [ $( $generics:tt )* ] $collection:ty => $interface:expr, $method:expr );* $(;)* ) => {
+        $(
+            impl<$( $generics )*> JavaCollection for $collection {
+                const INTERFACE_CLASS_NAME: &'static str = $interface;
+                const INTERFACE_SIGNATURE: &'static str = concat!("L", $interface, ";");
+                const UNMODIFIABLE_METHOD_NAME: &'static str = $method;
+                const UNMODIFIABLE_METHOD_SIGNATURE: &'static str =
+                    concat!("(L", $interface, ";)L", $interface, ";");
+            }
+        )*
+    };
+}
+
+impl_java_collection! {
+    [T] Vec<T> => "java/util/List", "unmodifiableList";
+    [T] VecDeque<T> => "java/util/Collection", "unmodifiableCollection";
+    [T, S] HashSet<T, S> => "java/util/Set", "unmodifiableSet";
+    [T] BTreeSet<T> => "java/util/Set", "unmodifiableSet";
+    [K, V, S] HashMap<K, V, S> => "java/util/Map", "unmodifiableMap";
+    [K, V] BTreeMap<K, V> => "java/util/Map", "unmodifiableMap";
+}
+
+macro_rules! impl_collection_contents_for_elements {
+    ( $( [ $( $generics:tt )* ] $collection:ty => $default_class:expr, $capacity:expr ),* $(,)* ) => {
+        $(
+            impl<'borrow, 'env, $( $generics )*> CollectionContents<'borrow, 'env> for $collection
+            where
+                'env: 'borrow,
+                T: TryIntoJava<'borrow, 'env>,
+                T::JavaType: Boxable<'borrow, 'env>,
+            {
+                const DEFAULT_CLASS_NAME: &'static str = $default_class;
+
+                fn initial_capacity(&self) -> Option<usize> {
+                    ($capacity)(self.len())
+                }
+
+                fn add_to(
+                    self,
+                    collection: &AutoLocal<'env, 'borrow>,
+                    env: &'borrow JnixEnv<'env>,
+                ) -> Result<(), Error> {
+                    add_elements(collection, Self::INTERFACE_CLASS_NAME, self, env)
+                }
+            }
+        )*
+    };
+}
+
+impl_collection_contents_for_elements! {
+    [T] Vec<T> => "java/util/ArrayList", Some,
+    [T] VecDeque<T> => "java/util/ArrayDeque", Some,
+    [T, S] HashSet<T, S> => "java/util/HashSet", |length| Some(hash_capacity(length)),
+    [T] BTreeSet<T> => "java/util/TreeSet", |_| None,
+}
+
+macro_rules! impl_collection_contents_for_entries {
+    ( $( [ $( $generics:tt )* ] $collection:ty => $default_class:expr, $capacity:expr ),* $(,)* ) => {
+        $(
+            impl<'borrow, 'env, $( $generics )*> CollectionContents<'borrow, 'env> for $collection
+            where
+                'env: 'borrow,
+                K: TryIntoJava<'borrow, 'env>,
+                K::JavaType: Boxable<'borrow, 'env>,
+                V: TryIntoJava<'borrow, 'env>,
+                V::JavaType: Boxable<'borrow, 'env>,
+            {
+                const DEFAULT_CLASS_NAME: &'static str = $default_class;
+
+                fn initial_capacity(&self) -> Option<usize> {
+                    ($capacity)(self.len())
+                }
+
+                fn add_to(
+                    self,
+                    collection: &AutoLocal<'env, 'borrow>,
+                    env: &'borrow JnixEnv<'env>,
+                ) -> Result<(), Error> {
+                    put_entries(collection, Self::INTERFACE_CLASS_NAME, self, env)
+                }
+            }
+        )*
+    };
+}
+
+impl_collection_contents_for_entries! {
+    [K, V, S] HashMap<K, V, S> => "java/util/HashMap", |length| Some(hash_capacity(length)),
+    [K, V] BTreeMap<K, V> => "java/util/TreeMap", |_| None,
+}
+
+impl<'borrow, 'env, T> IntoJavaCollection<'borrow, 'env> for T
+where
+    'env: 'borrow,
+    T: CollectionContents<'borrow, 'env>,
+{
+    fn into_java_collection(
+        self,
+        env: &'borrow JnixEnv<'env>,
+    ) -> Result<AutoLocal<'env, 'borrow>, Error> {
+        let collection = new_collection(T::DEFAULT_CLASS_NAME, self.initial_capacity(), env)?;
+
+        self.add_to(&collection, env)?;
+
+        Ok(collection)
+    }
+}
+
+/// Converts a Rust collection into an instance of the given Java collection class.
+///
+/// The class must implement the Java interface of the Rust collection (e.g. `java.util.List` for a
+/// `Vec<T>`), and must have a public constructor that receives the initial capacity as an `int` or
+/// one without parameters. The JNI signature is the signature of the interface.
+pub struct CustomCollection<T> {
+    class_name: &'static str,
+    contents: T,
+}
+
+impl<T> CustomCollection<T> {
+    /// Creates the wrapper for a collection, with the Java class name in JNI format (e.g.
+    /// `java/util/LinkedList`).
+    pub fn new(class_name: &'static str, contents: T) -> Self {
+        CustomCollection {
+            class_name,
+            contents,
+        }
+    }
+}
+
+impl<T: JavaCollection> JavaCollection for CustomCollection<T> {
+    const INTERFACE_CLASS_NAME: &'static str = T::INTERFACE_CLASS_NAME;
+    const INTERFACE_SIGNATURE: &'static str = T::INTERFACE_SIGNATURE;
+    const UNMODIFIABLE_METHOD_NAME: &'static str = T::UNMODIFIABLE_METHOD_NAME;
+    const UNMODIFIABLE_METHOD_SIGNATURE: &'static str = T::UNMODIFIABLE_METHOD_SIGNATURE;
+}
+
+impl<'borrow, 'env, T> IntoJavaCollection<'borrow, 'env> for CustomCollection<T>
+where
+    'env: 'borrow,
+    T: CollectionContents<'borrow, 'env>,
+{
+    fn into_java_collection(
+        self,
+        env: &'borrow JnixEnv<'env>,
+    ) -> Result<AutoLocal<'env, 'borrow>, Error> {
+        let capacity = self.contents.initial_capacity();
+        let collection = new_collection(self.class_name, capacity, env)?;
+
+        self.contents.add_to(&collection, env)?;
+
+        Ok(collection)
+    }
+}
+
+impl<'borrow, 'env, T> TryIntoJava<'borrow, 'env> for CustomCollection<T>
+where
+    'env: 'borrow,
+    T: CollectionContents<'borrow, 'env>,
+{
+    const JNI_SIGNATURE: &'static str = T::INTERFACE_SIGNATURE;
+
+    type JavaType = AutoLocal<'env, 'borrow>;
+
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        self.into_java_collection(env)
+    }
+}
+
+/// Converts a Rust collection into an unmodifiable view of the Java collection, created with the
+/// `java.util.Collections.unmodifiable*` methods.
+///
+/// The JNI signature is the signature of the collection's Java interface.
+pub struct Unmodifiable<T>(pub T);
+
+impl<'borrow, 'env, T> TryIntoJava<'borrow, 'env> for Unmodifiable<T>
+where
+    'env: 'borrow,
+    T: IntoJavaCollection<'borrow, 'env>,
+{
+    const JNI_SIGNATURE: &'static str = T::INTERFACE_SIGNATURE;
+
+    type JavaType = AutoLocal<'env, 'borrow>;
+
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        let collection = self.0.into_java_collection(env)?;
+
+        let class = env.try_get_class("java/util/Collections")?;
+        let method = env.get_cached_static_method_id(
+            "java/util/Collections",
+            T::UNMODIFIABLE_METHOD_NAME,
+            T::UNMODIFIABLE_METHOD_SIGNATURE,
+        )?;
+
+        let result = env
+            .call_static_method_unchecked(
+                &class,
+                method,
+                JavaType::Object(String::new()),
+                &[JValue::Object(collection.as_obj())],
+            )
+            .map_err(|error| env.convert_jni_error(error))?;
+
+        match result {
+            JValue::Object(object) => Ok(env.auto_local(object)),
+            value => Err(Error::InvalidReturnValue {
+                source: format!("Collections.{}", T::UNMODIFIABLE_METHOD_NAME),
+                value_type: value.type_name(),
+            }),
+        }
+    }
+}
+
+/// Creates an empty Java collection, with the initial capacity if there is one and the class has a
+/// constructor that receives it.
+pub(crate) fn new_collection<'borrow, 'env: 'borrow>(
+    class_name: &str,
+    capacity: Option<usize>,
+    env: &'borrow JnixEnv<'env>,
+) -> Result<AutoLocal<'env, 'borrow>, Error> {
+    let class = env.try_get_class(class_name)?;
+    let capacity_constructor = capacity.and_then(|capacity| {
+        env.get_cached_method_id(class_name, "<init>", "(I)V")
+            .ok()
+            .map(|constructor| (constructor, capacity))
+    });
+
+    let object = match capacity_constructor {
+        Some((constructor, capacity)) => {
+            env.new_object_unchecked(&class, constructor, &[JValue::Int(capacity as jint)])
+        }
+        None => {
+            let constructor = env.get_cached_method_id(class_name, "<init>", "()V")?;
+
+            env.new_object_unchecked(&class, constructor, &[])
+        }
+    }
+    .map_err(|error| env.convert_jni_error(error))?;
+
+    Ok(env.auto_local(object))
+}
+
+pub(crate) fn add_elements<'borrow, 'env, T>(
+    collection: &AutoLocal<'env, 'borrow>,
+    class_name: &str,
+    elements: impl IntoIterator<Item = T>,
+    env: &'borrow JnixEnv<'env>,
+) -> Result<(), Error>
+where
+    'env: 'borrow,
+    T: TryIntoJava<'borrow, 'env>,
+    T::JavaType: Boxable<'borrow, 'env>,
+{
+    let add_method = env.get_cached_method_id(class_name, "add", "(Ljava/lang/Object;)Z")?;
+
+    for element in elements {
+        let java_element = Boxed(element).try_into_java(env)?;
+
+        env.call_method_unchecked(
+            collection.as_obj(),
+            add_method,
+            JavaType::Primitive(Primitive::Boolean),
+            &[JValue::Object(java_element.as_obj())],
+        )
+        .map_err(|error| env.convert_jni_error(error))?;
+    }
+
+    Ok(())
+}
+
+pub(crate) fn put_entries<'borrow, 'env, K, V>(
+    map: &AutoLocal<'env, 'borrow>,
+    class_name: &str,
+    entries: impl IntoIterator<Item = (K, V)>,
+    env: &'borrow JnixEnv<'env>,
+) -> Result<(), Error>
+where
+    'env: 'borrow,
+    K: TryIntoJava<'borrow, 'env>,
+    K::JavaType: Boxable<'borrow, 'env>,
+    V: TryIntoJava<'borrow, 'env>,
+    V::JavaType: Boxable<'borrow, 'env>,
+{
+    let put_method = env.get_cached_method_id(
+        class_name,
+        "put",
+        "(Ljava/lang/Object;Ljava/lang/Object;)Ljava/lang/Object;",
+    )?;
+
+    for (key, value) in entries {
+        let java_key = Boxed(key).try_into_java(env)?;
+        let java_value = Boxed(value).try_into_java(env)?;
+
+        let previous_value = env
+            .call_method_unchecked(
+                map.as_obj(),
+                put_method,
+                JavaType::Object(String::new()),
+                &[
+                    JValue::Object(java_key.as_obj()),
+                    JValue::Object(java_value.as_obj()),
+                ],
+            )
+            .map_err(|error| env.convert_jni_error(error))?;
+
+        if let JValue::Object(previous_value) = previous_value {
+            env.delete_local_ref(previous_value)
+                .map_err(|error| env.convert_jni_error(error))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Calculates the initial capacity of a Java hash collection so that it can store the given number
+/// of elements without being resized, considering the default load factor of 0.75.
+pub(crate) fn hash_capacity(length: usize) -> usize {
+    length + length / 3 + 1
+}
@@ -0,0 +1,169 @@
+//! Helpers to build JNI method signatures at compile time.
+//!
+//! These are used by the derive macros to build constructor signatures as constants, so that no
+//! memory is allocated for the signature on every conversion. A signature is built in three steps,
+//! because the length of the resulting array must be known before it can be created:
+//!
+//! ```ignore
+//! const PARAMETERS: &[&str] = &["I", "Ljava/lang/String;"];
+//! const LENGTH: usize = constructor_signature_length(PARAMETERS);
+//! const BYTES: [u8; LENGTH] = build_constructor_signature(PARAMETERS);
+//! const SIGNATURE: &str = signature_as_str(&BYTES);
+//! ```
+//!
+//! Signatures that depend on generic parameters can't be built in a `const` item inside a generic
+//! function. They are built in associated constants instead, and stored in a [`SignatureBuffer`],
+//! so they are still built only once for each set of generic parameters.
+//!
+//! [`SignatureBuffer`]: struct.SignatureBuffer.html
+
+use crate::FromJava;
+use std::marker::PhantomData;
+
+/// Calculates the length of the signature of a constructor that receives the given parameters.
+pub const fn constructor_signature_length(parameters: &[&str]) -> usize {
+    let mut length = "()V".len();
+    let mut index = 0;
+
+    while index < parameters.len() {
+        length += parameters[index].len();
+        index += 1;
+    }
+
+    length
+}
+
+/// Builds the signature of a constructor that receives the given parameters.
+///
+/// The `LENGTH` must be calculated with [`constructor_signature_length`].
+///
+/// [`constructor_signature_length`]: fn.constructor_signature_length.html
+pub const fn build_constructor_signature<const LENGTH: usize>(parameters: &[&str]) -> [u8; LENGTH] {
+    let mut signature = [0u8; LENGTH];
+    let mut position = 0;
+    let mut parameter_index = 0;
+
+    signature[position] = b'(';
+    position += 1;
+
+    while parameter_index < parameters.len() {
+        let parameter = parameters[parameter_index].as_bytes();
+        let mut byte_index = 0;
+
+        while byte_index < parameter.len() {
+            signature[position] = parameter[byte_index];
+            position += 1;
+            byte_index += 1;
+        }
+
+        parameter_index += 1;
+    }
+
+    signature[position] = b')';
+    signature[position + 1] = b'V';
+
+    signature
+}
+
+/// Converts a signature built with [`build_constructor_signature`] into a string.
+///
+/// [`build_constructor_signature`]: fn.build_constructor_signature.html
+pub const fn signature_as_str(signature: &[u8]) -> &str {
+    match std::str::from_utf8(signature) {
+        Ok(signature) => signature,
+        Err(_) => panic!("Invalid UTF-8 in JNI signature"),
+    }
+}
+
+/// Maximum length of a signature stored in a [`SignatureBuffer`].
+///
+/// [`SignatureBuffer`]: struct.SignatureBuffer.html
+pub const MAX_SIGNATURE_LENGTH: usize = 256;
+
+/// A signature built at compile time whose length depends on generic parameters.
+///
+/// Array lengths can't depend on generic parameters, so the signature is stored in a buffer with
+/// a fixed capacity.
+pub struct SignatureBuffer {
+    bytes: [u8; MAX_SIGNATURE_LENGTH],
+    length: usize,
+}
+
+impl SignatureBuffer {
+    const EMPTY: Self = SignatureBuffer {
+        bytes: [0; MAX_SIGNATURE_LENGTH],
+        length: 0,
+    };
+
+    /// Returns the signature as a string.
+    ///
+    /// The buffer must be `'static`, which is the case for a reference to a buffer built in a
+    /// constant expression:
+    ///
+    /// ```ignore
+    /// const SIGNATURE: &str = SignatureBuffer::as_str(&array_signature(T::JNI_SIGNATURE));
+    /// ```
+    pub const fn as_str(&'static self) -> &'static str {
+        signature_as_str(self.bytes.split_at(self.length).0)
+    }
+}
+
+/// Builds the signature of an array with elements of the given signature.
+pub const fn array_signature(element_signature: &str) -> SignatureBuffer {
+    append(SignatureBuffer::EMPTY, &["[", element_signature])
+}
+
+/// Builds the signature of a method without parameters that returns a value of the given
+/// signature.
+pub const fn getter_signature(return_signature: &str) -> SignatureBuffer {
+    append(SignatureBuffer::EMPTY, &["()", return_signature])
+}
+
+/// Builds the signature of a constructor that receives the given parameters, when their
+/// signatures depend on generic parameters.
+pub const fn generic_constructor_signature(parameters: &[&str]) -> SignatureBuffer {
+    let signature = append(SignatureBuffer::EMPTY, &["("]);
+    let signature = append(signature, parameters);
+
+    append(signature, &[")V"])
+}
+
+/// Captures the type of a value, so that a signature can be built for it at compile time.
+pub fn type_of<T>(_: &T) -> PhantomData<T> {
+    PhantomData
+}
+
+const fn append(mut signature: SignatureBuffer, parts: &[&str]) -> SignatureBuffer {
+    let mut part_index = 0;
+
+    while part_index < parts.len() {
+        let part = parts[part_index].as_bytes();
+        let mut byte_index = 0;
+
+        assert!(
+            signature.length + part.len() <= MAX_SIGNATURE_LENGTH,
+            "JNI signature is too long"
+        );
+
+        while byte_index < part.len() {
+            signature.bytes[signature.length] = part[byte_index];
+            signature.length += 1;
+            byte_index += 1;
+        }
+
+        part_index += 1;
+    }
+
+    signature
+}
+
+/// The signature of a getter method that returns a `T`.
+///
+/// It is built once for each type, so the derive macros can use it for fields whose type depends
+/// on generic parameters.
+pub struct GetterSignature<'env, T>(PhantomData<(&'env (), T)>);
+
+impl<'env, T: FromJava<'env>> GetterSignature<'env, T> {
+    pub const SIGNATURE: &'static str =
+        SignatureBuffer::as_str(&getter_signature(T::JNI_SIGNATURE));
+}
@@ -0,0 +1,110 @@
+use crate::JavaException;
+use jni::errors::Error as JniError;
+use std::{borrow::Cow, error::Error as StdError, fmt};
+
+/// Errors that can happen while converting values between Rust and Java.
+#[derive(Debug)]
+pub enum Error {
+    /// A Java class could not be found.
+    ClassNotFound(String),
+
+    /// A method could not be found in a Java class.
+    MethodNotFound {
+        class_name: String,
+        method_name: String,
+        signature: String,
+    },
+
+    /// A field could not be found in a Java class.
+    FieldNotFound {
+        class_name: String,
+        field_name: String,
+        signature: String,
+    },
+
+    /// A Java exception was thrown while calling into the JVM.
+    JavaException(JavaException),
+
+    /// A call into the JVM returned a value of an unexpected type.
+    InvalidReturnValue {
+        source: String,
+        value_type: &'static str,
+    },
+
+    /// A Rust value can't be represented by the Java type it is converted into.
+    UnrepresentableValue {
+        value: String,
+        java_type: &'static str,
+    },
+
+    /// A Rust error that should be thrown as a Java exception, like the error of a `Result` that is
+    /// converted into its `Ok` value.
+    Exception {
+        class_name: Cow<'static, str>,
+        message: String,
+    },
+
+    /// Any other JNI error.
+    Jni(JniError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::ClassNotFound(class_name) => {
+                write!(formatter, "Failed to find {} Java class", class_name)
+            }
+            Error::MethodNotFound {
+                class_name,
+                method_name,
+                signature,
+            } => write!(
+                formatter,
+                "Failed to find {}.{}{} Java method",
+                class_name, method_name, signature
+            ),
+            Error::FieldNotFound {
+                class_name,
+                field_name,
+                signature,
+            } => write!(
+                formatter,
+                "Failed to find {}.{} Java field with type {}",
+                class_name, field_name, signature
+            ),
+            Error::JavaException(exception) => {
+                write!(formatter, "A Java exception was thrown: {}", exception)
+            }
+            Error::InvalidReturnValue { source, value_type } => write!(
+                formatter,
+                "{} returned an invalid value of type {}",
+                source, value_type
+            ),
+            Error::UnrepresentableValue { value, java_type } => write!(
+                formatter,
+                "{} can't be represented as a Java {}",
+                value, java_type
+            ),
+            Error::Exception {
+                class_name,
+                message,
+            } => write!(
+                formatter,
+                "Error to be thrown as a {} Java exception: {}",
+                class_name.replace("/", "."),
+                message
+            ),
+            Error::Jni(error) => write!(formatter, "JNI error: {}", error),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Error::JavaException(exception) => Some(exception),
+            Error::Jni(error) => Some(error),
+            _ => None,
+        }
+    }
+}
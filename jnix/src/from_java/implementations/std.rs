@@ -0,0 +1,232 @@
+use crate::{java_string::java_string_to_string, FromJava, JnixEnv};
+use jni::{
+    objects::{JList, JObject},
+    signature::{JavaType, Primitive},
+    sys::{jboolean, jdouble, jint, jshort, JNI_FALSE},
+};
+use std::{
+    convert::TryFrom,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+};
+
+impl<'env> FromJava<'env> for bool {
+    const JNI_SIGNATURE: &'static str = "Z";
+
+    type JavaType = jboolean;
+
+    fn from_java(_: &JnixEnv<'env>, source: Self::JavaType) -> Self {
+        source != JNI_FALSE
+    }
+}
+
+impl<'env> FromJava<'env> for i16 {
+    const JNI_SIGNATURE: &'static str = "S";
+
+    type JavaType = jshort;
+
+    fn from_java(_: &JnixEnv<'env>, source: Self::JavaType) -> Self {
+        source
+    }
+}
+
+impl<'env> FromJava<'env> for i32 {
+    const JNI_SIGNATURE: &'static str = "I";
+
+    type JavaType = jint;
+
+    fn from_java(_: &JnixEnv<'env>, source: Self::JavaType) -> Self {
+        source
+    }
+}
+
+impl<'env> FromJava<'env> for f64 {
+    const JNI_SIGNATURE: &'static str = "D";
+
+    type JavaType = jdouble;
+
+    fn from_java(_: &JnixEnv<'env>, source: Self::JavaType) -> Self {
+        source
+    }
+}
+
+impl<'env> FromJava<'env> for Box<[u8]> {
+    const JNI_SIGNATURE: &'static str = "[B";
+
+    type JavaType = JObject<'env>;
+
+    fn from_java(env: &JnixEnv<'env>, source: Self::JavaType) -> Self {
+        env.convert_byte_array(source.into_inner())
+            .expect("Failed to copy bytes from Java array")
+            .into_boxed_slice()
+    }
+}
+
+impl<'env, const N: usize> FromJava<'env> for [u8; N] {
+    const JNI_SIGNATURE: &'static str = "[B";
+
+    type JavaType = JObject<'env>;
+
+    fn from_java(env: &JnixEnv<'env>, source: Self::JavaType) -> Self {
+        let data = <Box<[u8]>>::from_java(env, source);
+
+        assert_eq!(
+            data.len(),
+            N,
+            "Java array has an unexpected number of elements"
+        );
+
+        let mut array = [0; N];
+
+        array.copy_from_slice(&data);
+        array
+    }
+}
+
+impl<'env, T> FromJava<'env> for Option<T>
+where
+    T: FromJava<'env, JavaType = JObject<'env>>,
+{
+    const JNI_SIGNATURE: &'static str = T::JNI_SIGNATURE;
+
+    type JavaType = JObject<'env>;
+
+    fn from_java(env: &JnixEnv<'env>, source: Self::JavaType) -> Self {
+        if source.is_null() {
+            None
+        } else {
+            Some(T::from_java(env, source))
+        }
+    }
+}
+
+impl<'env, T> FromJava<'env> for Vec<T>
+where
+    T: FromJava<'env, JavaType = JObject<'env>>,
+{
+    const JNI_SIGNATURE: &'static str = "Ljava/util/List;";
+
+    type JavaType = JObject<'env>;
+
+    fn from_java(env: &JnixEnv<'env>, source: Self::JavaType) -> Self {
+        let list = JList::from_env(env, source).expect("Failed to create JList from List");
+        let size = list.size().expect("Failed to get size of List");
+        let mut elements = Vec::with_capacity(size as usize);
+
+        for index in 0..size {
+            let element = list
+                .get(index)
+                .expect("Failed to get element from List")
+                .unwrap_or_else(JObject::null);
+
+            elements.push(T::from_java(env, element));
+            env.delete_local_ref(element)
+                .expect("Failed to delete local reference to List element");
+        }
+
+        elements
+    }
+}
+
+impl<'env> FromJava<'env> for String {
+    const JNI_SIGNATURE: &'static str = "Ljava/lang/String;";
+
+    type JavaType = JObject<'env>;
+
+    fn from_java(env: &JnixEnv<'env>, source: Self::JavaType) -> Self {
+        java_string_to_string(env, source).expect("Failed to convert from Java String")
+    }
+}
+
+impl<'env> FromJava<'env> for Ipv4Addr {
+    const JNI_SIGNATURE: &'static str = "Ljava/net/InetAddress;";
+
+    type JavaType = JObject<'env>;
+
+    fn from_java(env: &JnixEnv<'env>, source: Self::JavaType) -> Self {
+        match IpAddr::from_java(env, source) {
+            IpAddr::V4(address) => address,
+            IpAddr::V6(_) => panic!("InetAddress Java object is not an IPv4 address"),
+        }
+    }
+}
+
+impl<'env> FromJava<'env> for Ipv6Addr {
+    const JNI_SIGNATURE: &'static str = "Ljava/net/InetAddress;";
+
+    type JavaType = JObject<'env>;
+
+    fn from_java(env: &JnixEnv<'env>, source: Self::JavaType) -> Self {
+        match IpAddr::from_java(env, source) {
+            IpAddr::V6(address) => address,
+            IpAddr::V4(_) => panic!("InetAddress Java object is not an IPv6 address"),
+        }
+    }
+}
+
+impl<'env> FromJava<'env> for IpAddr {
+    const JNI_SIGNATURE: &'static str = "Ljava/net/InetAddress;";
+
+    type JavaType = JObject<'env>;
+
+    fn from_java(env: &JnixEnv<'env>, source: Self::JavaType) -> Self {
+        let get_address = env
+            .get_cached_method_id("java/net/InetAddress", "getAddress", "()[B")
+            .unwrap_or_else(|error| panic!("{}", error));
+        let octets = env
+            .call_method_unchecked(
+                source,
+                get_address,
+                JavaType::Array(Box::new(JavaType::Primitive(Primitive::Byte))),
+                &[],
+            )
+            .and_then(|octets| octets.l())
+            .expect("Failed to get octets of InetAddress Java object");
+
+        let octet_data = <Box<[u8]>>::from_java(env, octets);
+
+        env.delete_local_ref(octets)
+            .expect("Failed to delete local reference to IP address octets array");
+
+        match octet_data.len() {
+            4 => IpAddr::V4(<[u8; 4]>::try_from(&*octet_data).unwrap().into()),
+            16 => IpAddr::V6(<[u8; 16]>::try_from(&*octet_data).unwrap().into()),
+            count => panic!("InetAddress Java object has {} octets", count),
+        }
+    }
+}
+
+impl<'env> FromJava<'env> for SocketAddr {
+    const JNI_SIGNATURE: &'static str = "Ljava/net/InetSocketAddress;";
+
+    type JavaType = JObject<'env>;
+
+    fn from_java(env: &JnixEnv<'env>, source: Self::JavaType) -> Self {
+        let get_address = env
+            .get_cached_method_id(
+                "java/net/InetSocketAddress",
+                "getAddress",
+                "()Ljava/net/InetAddress;",
+            )
+            .unwrap_or_else(|error| panic!("{}", error));
+        let get_port = env
+            .get_cached_method_id("java/net/InetSocketAddress", "getPort", "()I")
+            .unwrap_or_else(|error| panic!("{}", error));
+
+        let ip_address = env
+            .call_method_unchecked(source, get_address, JavaType::Object(String::new()), &[])
+            .and_then(|ip_address| ip_address.l())
+            .expect("Failed to get IP address from InetSocketAddress Java object");
+
+        let port = env
+            .call_method_unchecked(source, get_port, JavaType::Primitive(Primitive::Int), &[])
+            .and_then(|port| port.i())
+            .expect("Failed to get port from InetSocketAddress Java object");
+
+        let ip = IpAddr::from_java(env, ip_address);
+
+        env.delete_local_ref(ip_address)
+            .expect("Failed to delete local reference to InetAddress Java object");
+
+        SocketAddr::new(ip, port as u16)
+    }
+}
@@ -0,0 +1,11 @@
+mod implementations;
+
+use crate::JnixEnv;
+
+pub trait FromJava<'env> {
+    const JNI_SIGNATURE: &'static str;
+
+    type JavaType;
+
+    fn from_java(env: &JnixEnv<'env>, source: Self::JavaType) -> Self;
+}
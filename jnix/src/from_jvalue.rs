@@ -0,0 +1,47 @@
+use jni::{
+    objects::{JObject, JValue},
+    sys::{jboolean, jbyte, jchar, jdouble, jfloat, jint, jlong, jshort},
+};
+
+pub trait FromJValue<'env> {
+    fn from_jvalue(value: JValue<'env>) -> Self;
+}
+
+impl<'env> FromJValue<'env> for JObject<'env> {
+    fn from_jvalue(value: JValue<'env>) -> Self {
+        match value {
+            JValue::Object(object) => object,
+            value => panic!("Expected a Java object, but got a {}", value.type_name()),
+        }
+    }
+}
+
+macro_rules! impl_for_primitives {
+    ( $( $primitive:ty => $variant:ident ),* $(,)* ) => {
+        $(
+            impl<'env> FromJValue<'env> for $primitive {
+                fn from_jvalue(value: JValue<'env>) -> Self {
+                    match value {
+                        JValue::$variant(primitive) => primitive,
+                        value => panic!(
+                            "Expected a Java {}, but got a {}",
+                            stringify!($variant).to_lowercase(),
+                            value.type_name(),
+                        ),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_for_primitives!(
+    jboolean => Bool,
+    jbyte => Byte,
+    jchar => Char,
+    jshort => Short,
+    jint => Int,
+    jlong => Long,
+    jfloat => Float,
+    jdouble => Double,
+);
@@ -0,0 +1,208 @@
+use crate::{Error, JnixEnv, TryIntoJava};
+use jni::{
+    objects::{AutoLocal, JObject},
+    sys::{
+        jboolean, jbooleanArray, jbyte, jbyteArray, jchar, jcharArray, jdouble, jdoubleArray,
+        jfloat, jfloatArray, jint, jintArray, jlong, jlongArray, jobjectArray, jshort, jshortArray,
+        jsize,
+    },
+};
+
+/// Converted Java values that can be stored in a primitive Java array.
+pub trait ArrayElement<'borrow, 'env: 'borrow>: Sized {
+    const ARRAY_JNI_SIGNATURE: &'static str;
+
+    /// Creates a Java array with a copy of the elements.
+    fn new_array(
+        elements: &[Self],
+        env: &'borrow JnixEnv<'env>,
+    ) -> Result<AutoLocal<'env, 'borrow>, Error>;
+}
+
+macro_rules! impl_array_element {
+    ( $( $element:ty => $array:ty, $signature:expr, $new_array:ident, $set_region:ident ),* $(,)* ) => {
+        $(
+            impl<'borrow, 'env: 'borrow> ArrayElement<'borrow, 'env> for $element {
+                const ARRAY_JNI_SIGNATURE: &'static str = $signature;
+
+                fn new_array(
+                    elements: &[Self],
+                    env: &'borrow JnixEnv<'env>,
+                ) -> Result<AutoLocal<'env, 'borrow>, Error> {
+                    let array = env
+                        .$new_array(elements.len() as jsize)
+                        .map_err(|error| env.convert_jni_error(error))?;
+                    let array = env.auto_local(JObject::from(array));
+
+                    env.$set_region(array.as_obj().into_inner() as $array, 0, elements)
+                        .map_err(|error| env.convert_jni_error(error))?;
+
+                    Ok(array)
+                }
+            }
+        )*
+    };
+}
+
+impl_array_element! {
+    jboolean => jbooleanArray, "[Z", new_boolean_array, set_boolean_array_region,
+    jbyte => jbyteArray, "[B", new_byte_array, set_byte_array_region,
+    jchar => jcharArray, "[C", new_char_array, set_char_array_region,
+    jshort => jshortArray, "[S", new_short_array, set_short_array_region,
+    jint => jintArray, "[I", new_int_array, set_int_array_region,
+    jlong => jlongArray, "[J", new_long_array, set_long_array_region,
+    jfloat => jfloatArray, "[F", new_float_array, set_float_array_region,
+    jdouble => jdoubleArray, "[D", new_double_array, set_double_array_region,
+}
+
+/// Converted Java values that can be stored in a typed Java array.
+///
+/// Primitive values are copied into a primitive Java array, while objects are stored in an array
+/// of the element's class. For example, a `[i32; 3]` becomes an `int[]`, and a `Box<[String]>`
+/// becomes a `String[]`.
+pub trait TypedArrayElement<'borrow, 'env: 'borrow>: Sized {
+    fn new_typed_array<T>(
+        elements: Vec<T>,
+        element_signature: &str,
+        env: &'borrow JnixEnv<'env>,
+    ) -> Result<AutoLocal<'env, 'borrow>, Error>
+    where
+        T: TryIntoJava<'borrow, 'env, JavaType = Self>;
+}
+
+impl<'borrow, 'env: 'borrow> TypedArrayElement<'borrow, 'env> for AutoLocal<'env, 'borrow> {
+    fn new_typed_array<T>(
+        elements: Vec<T>,
+        element_signature: &str,
+        env: &'borrow JnixEnv<'env>,
+    ) -> Result<AutoLocal<'env, 'borrow>, Error>
+    where
+        T: TryIntoJava<'borrow, 'env, JavaType = Self>,
+    {
+        let element_class_name = if element_signature.starts_with('L') {
+            element_signature
+                .trim_start_matches('L')
+                .trim_end_matches(';')
+        } else {
+            element_signature
+        };
+
+        let element_class = env.try_get_class(element_class_name)?;
+        let array = env
+            .new_object_array(elements.len() as jsize, &element_class, JObject::null())
+            .map_err(|error| env.convert_jni_error(error))?;
+        let array = env.auto_local(JObject::from(array));
+
+        for (index, element) in elements.into_iter().enumerate() {
+            let java_element = element.try_into_java(env)?;
+
+            env.set_object_array_element(
+                array.as_obj().into_inner() as jobjectArray,
+                index as jsize,
+                java_element.as_obj(),
+            )
+            .map_err(|error| env.convert_jni_error(error))?;
+        }
+
+        Ok(array)
+    }
+}
+
+macro_rules! impl_typed_array_elements_for_primitives {
+    ( $( $element:ty ),* $(,)* ) => {
+        $(
+            impl<'borrow, 'env: 'borrow> TypedArrayElement<'borrow, 'env> for $element {
+                fn new_typed_array<T>(
+                    elements: Vec<T>,
+                    _: &str,
+                    env: &'borrow JnixEnv<'env>,
+                ) -> Result<AutoLocal<'env, 'borrow>, Error>
+                where
+                    T: TryIntoJava<'borrow, 'env, JavaType = Self>,
+                {
+                    let java_elements = elements
+                        .into_iter()
+                        .map(|element| element.try_into_java(env))
+                        .collect::<Result<Vec<_>, _>>()?;
+
+                    ArrayElement::new_array(&java_elements, env)
+                }
+            }
+        )*
+    };
+}
+
+impl_typed_array_elements_for_primitives!(
+    jboolean, jbyte, jchar, jshort, jint, jlong, jfloat, jdouble
+);
+
+/// Rust primitive values that can be copied from a slice into a primitive Java array.
+///
+/// Values with the same layout as their converted Java type are copied directly from the slice,
+/// without any intermediate buffer.
+pub trait SliceElement<'borrow, 'env: 'borrow>: TryIntoJava<'borrow, 'env> + Sized {
+    /// Creates a Java array with a copy of the elements.
+    fn new_array_from_slice(
+        elements: &[Self],
+        env: &'borrow JnixEnv<'env>,
+    ) -> Result<AutoLocal<'env, 'borrow>, Error>;
+}
+
+macro_rules! impl_slice_elements_with_java_layout {
+    ( $( $element:ty => $java_type:ty ),* $(,)* ) => {
+        $(
+            impl<'borrow, 'env: 'borrow> SliceElement<'borrow, 'env> for $element {
+                fn new_array_from_slice(
+                    elements: &[Self],
+                    env: &'borrow JnixEnv<'env>,
+                ) -> Result<AutoLocal<'env, 'borrow>, Error> {
+                    // The element has the same size and alignment as the Java type, and the
+                    // conversion only reinterprets its bits.
+                    let java_elements = unsafe {
+                        std::slice::from_raw_parts(
+                            elements.as_ptr() as *const $java_type,
+                            elements.len(),
+                        )
+                    };
+
+                    ArrayElement::new_array(java_elements, env)
+                }
+            }
+        )*
+    };
+}
+
+impl_slice_elements_with_java_layout! {
+    i8 => jbyte,
+    u8 => jbyte,
+    i16 => jshort,
+    u16 => jshort,
+    i32 => jint,
+    u32 => jint,
+    i64 => jlong,
+    u64 => jlong,
+    f32 => jfloat,
+    f64 => jdouble,
+}
+
+macro_rules! impl_converted_slice_elements {
+    ( $( $element:ty ),* $(,)* ) => {
+        $(
+            impl<'borrow, 'env: 'borrow> SliceElement<'borrow, 'env> for $element {
+                fn new_array_from_slice(
+                    elements: &[Self],
+                    env: &'borrow JnixEnv<'env>,
+                ) -> Result<AutoLocal<'env, 'borrow>, Error> {
+                    let java_elements = elements
+                        .iter()
+                        .map(|&element| element.try_into_java(env))
+                        .collect::<Result<Vec<_>, _>>()?;
+
+                    ArrayElement::new_array(&java_elements, env)
+                }
+            }
+        )*
+    };
+}
+
+impl_converted_slice_elements!(bool, char, isize, usize);
@@ -0,0 +1,114 @@
+use crate::{
+    collections::{add_elements, hash_capacity, new_collection, put_entries},
+    Boxable, Error, JnixEnv, TryIntoJava,
+};
+use jni::objects::AutoLocal;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    hash::BuildHasher,
+};
+
+impl<'borrow, 'env, K, V, S> TryIntoJava<'borrow, 'env> for HashMap<K, V, S>
+where
+    'env: 'borrow,
+    K: TryIntoJava<'borrow, 'env>,
+    K::JavaType: Boxable<'borrow, 'env>,
+    V: TryIntoJava<'borrow, 'env>,
+    V::JavaType: Boxable<'borrow, 'env>,
+    S: BuildHasher,
+{
+    const JNI_SIGNATURE: &'static str = "Ljava/util/HashMap;";
+
+    type JavaType = AutoLocal<'env, 'borrow>;
+
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        let capacity = hash_capacity(self.len());
+        let map = new_collection("java/util/HashMap", Some(capacity), env)?;
+
+        put_entries(&map, "java/util/HashMap", self, env)?;
+
+        Ok(map)
+    }
+}
+
+// `java.util.TreeMap` sorts its keys by their natural ordering, which may differ from the ordering
+// of the Rust keys.
+impl<'borrow, 'env, K, V> TryIntoJava<'borrow, 'env> for BTreeMap<K, V>
+where
+    'env: 'borrow,
+    K: TryIntoJava<'borrow, 'env>,
+    K::JavaType: Boxable<'borrow, 'env>,
+    V: TryIntoJava<'borrow, 'env>,
+    V::JavaType: Boxable<'borrow, 'env>,
+{
+    const JNI_SIGNATURE: &'static str = "Ljava/util/TreeMap;";
+
+    type JavaType = AutoLocal<'env, 'borrow>;
+
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        let map = new_collection("java/util/TreeMap", None, env)?;
+
+        put_entries(&map, "java/util/TreeMap", self, env)?;
+
+        Ok(map)
+    }
+}
+
+impl<'borrow, 'env, T, S> TryIntoJava<'borrow, 'env> for HashSet<T, S>
+where
+    'env: 'borrow,
+    T: TryIntoJava<'borrow, 'env>,
+    T::JavaType: Boxable<'borrow, 'env>,
+    S: BuildHasher,
+{
+    const JNI_SIGNATURE: &'static str = "Ljava/util/HashSet;";
+
+    type JavaType = AutoLocal<'env, 'borrow>;
+
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        let capacity = hash_capacity(self.len());
+        let set = new_collection("java/util/HashSet", Some(capacity), env)?;
+
+        add_elements(&set, "java/util/HashSet", self, env)?;
+
+        Ok(set)
+    }
+}
+
+impl<'borrow, 'env, T> TryIntoJava<'borrow, 'env> for BTreeSet<T>
+where
+    'env: 'borrow,
+    T: TryIntoJava<'borrow, 'env>,
+    T::JavaType: Boxable<'borrow, 'env>,
+{
+    const JNI_SIGNATURE: &'static str = "Ljava/util/TreeSet;";
+
+    type JavaType = AutoLocal<'env, 'borrow>;
+
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        let set = new_collection("java/util/TreeSet", None, env)?;
+
+        add_elements(&set, "java/util/TreeSet", self, env)?;
+
+        Ok(set)
+    }
+}
+
+impl<'borrow, 'env, T> TryIntoJava<'borrow, 'env> for VecDeque<T>
+where
+    'env: 'borrow,
+    T: TryIntoJava<'borrow, 'env>,
+    T::JavaType: Boxable<'borrow, 'env>,
+{
+    const JNI_SIGNATURE: &'static str = "Ljava/util/ArrayDeque;";
+
+    type JavaType = AutoLocal<'env, 'borrow>;
+
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        let deque = new_collection("java/util/ArrayDeque", Some(self.len()), env)?;
+
+        add_elements(&deque, "java/util/ArrayDeque", self, env)?;
+
+        Ok(deque)
+    }
+}
@@ -1 +1,4 @@
+mod collections;
 mod std;
+mod time;
+mod tuples;
@@ -1,255 +1,427 @@
-use crate::{AsJValue, IntoJava, JnixEnv};
+use crate::{
+    java_string::new_java_string,
+    os_str::{JavaFile, OsStrPolicy},
+    unsigned::BigInteger,
+    Array, ArrayElement, AsJValue, Boxable, Boxed, Error, IntoJavaException, JnixEnv, SliceElement,
+    TryIntoJava, TypedArrayElement,
+};
 use jni::{
-    objects::{AutoLocal, JList, JObject, JValue},
-    signature::JavaType,
-    sys::{jboolean, jdouble, jint, jshort, jsize, JNI_FALSE, JNI_TRUE},
+    objects::{AutoLocal, JObject, JValue},
+    signature::{JavaType, Primitive},
+    sys::{jboolean, jbyte, jchar, jdouble, jfloat, jint, jlong, jshort, JNI_FALSE, JNI_TRUE},
+};
+use std::{
+    borrow::Cow,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::Arc,
 };
-use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 
-impl<'borrow, 'env: 'borrow> IntoJava<'borrow, 'env> for bool {
+impl<'borrow, 'env: 'borrow> TryIntoJava<'borrow, 'env> for () {
+    const JNI_SIGNATURE: &'static str = "V";
+
+    type JavaType = ();
+
+    fn try_into_java(self, _: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        Ok(())
+    }
+}
+
+impl<'borrow, 'env: 'borrow> TryIntoJava<'borrow, 'env> for bool {
     const JNI_SIGNATURE: &'static str = "Z";
 
     type JavaType = jboolean;
 
-    fn into_java(self, _: &'borrow JnixEnv<'env>) -> Self::JavaType {
-        if self {
-            JNI_TRUE
-        } else {
-            JNI_FALSE
-        }
+    fn try_into_java(self, _: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        Ok(if self { JNI_TRUE } else { JNI_FALSE })
     }
 }
 
-impl<'borrow, 'env: 'borrow> IntoJava<'borrow, 'env> for i16 {
-    const JNI_SIGNATURE: &'static str = "S";
+// Unsigned integers are converted into the signed Java type with the same width, reinterpreting
+// their bits. For example, `u8::MAX` becomes `(byte) -1`, which Java code can read back with
+// `Byte.toUnsignedInt`. Other policies can be selected per field with the `jnix(unsigned = ...)`
+// attribute, using the wrappers in the `unsigned` module.
+macro_rules! impl_into_java_for_primitives {
+    ( $( $primitive:ty => $java_type:ty, $signature:expr ),* $(,)* ) => {
+        $(
+            impl<'borrow, 'env: 'borrow> TryIntoJava<'borrow, 'env> for $primitive {
+                const JNI_SIGNATURE: &'static str = $signature;
 
-    type JavaType = jshort;
+                type JavaType = $java_type;
 
-    fn into_java(self, _: &'borrow JnixEnv<'env>) -> Self::JavaType {
-        self as jshort
-    }
+                fn try_into_java(self, _: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+                    Ok(self as $java_type)
+                }
+            }
+        )*
+    };
 }
 
-impl<'borrow, 'env: 'borrow> IntoJava<'borrow, 'env> for i32 {
-    const JNI_SIGNATURE: &'static str = "I";
+impl_into_java_for_primitives! {
+    i8 => jbyte, "B",
+    u8 => jbyte, "B",
+    i16 => jshort, "S",
+    u16 => jshort, "S",
+    i32 => jint, "I",
+    u32 => jint, "I",
+    i64 => jlong, "J",
+    u64 => jlong, "J",
+    isize => jlong, "J",
+    usize => jlong, "J",
+    f32 => jfloat, "F",
+    f64 => jdouble, "D",
+}
 
-    type JavaType = jint;
+impl<'borrow, 'env: 'borrow> TryIntoJava<'borrow, 'env> for char {
+    const JNI_SIGNATURE: &'static str = "C";
 
-    fn into_java(self, _: &'borrow JnixEnv<'env>) -> Self::JavaType {
-        self as jint
+    type JavaType = jchar;
+
+    fn try_into_java(self, _: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        let mut utf16 = [0; 2];
+
+        match self.encode_utf16(&mut utf16) {
+            [code_unit] => Ok(*code_unit),
+            _ => Err(Error::UnrepresentableValue {
+                value: format!("{:?}", self),
+                java_type: "char",
+            }),
+        }
     }
 }
 
-impl<'borrow, 'env: 'borrow> IntoJava<'borrow, 'env> for f64 {
-    const JNI_SIGNATURE: &'static str = "D";
+macro_rules! impl_into_java_for_wide_integers {
+    ( $( $integer:ty ),* $(,)* ) => {
+        $(
+            impl<'borrow, 'env: 'borrow> TryIntoJava<'borrow, 'env> for $integer {
+                const JNI_SIGNATURE: &'static str = "Ljava/math/BigInteger;";
 
-    type JavaType = jdouble;
+                type JavaType = AutoLocal<'env, 'borrow>;
 
-    fn into_java(self, _: &'borrow JnixEnv<'env>) -> Self::JavaType {
-        self as jdouble
-    }
+                fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+                    BigInteger(self).try_into_java(env)
+                }
+            }
+        )*
+    };
 }
 
-impl<'borrow, 'env: 'borrow> IntoJava<'borrow, 'env> for &'_ [u8] {
-    const JNI_SIGNATURE: &'static str = "[B";
+impl_into_java_for_wide_integers!(i128, u128);
+
+impl<'borrow, 'env, T> TryIntoJava<'borrow, 'env> for &'_ [T]
+where
+    'env: 'borrow,
+    T: SliceElement<'borrow, 'env>,
+    T::JavaType: ArrayElement<'borrow, 'env>,
+{
+    const JNI_SIGNATURE: &'static str =
+        <T::JavaType as ArrayElement<'borrow, 'env>>::ARRAY_JNI_SIGNATURE;
 
     type JavaType = AutoLocal<'env, 'borrow>;
 
-    fn into_java(self, env: &'borrow JnixEnv<'env>) -> Self::JavaType {
-        let size = self.len();
-        let array = env
-            .new_byte_array(size as jsize)
-            .expect("Failed to create a Java array of bytes");
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        T::new_array_from_slice(self, env)
+    }
+}
 
-        let data = unsafe { std::slice::from_raw_parts(self.as_ptr() as *const i8, size) };
+impl<'borrow, 'env, T> TryIntoJava<'borrow, 'env> for Box<[T]>
+where
+    'env: 'borrow,
+    T: TryIntoJava<'borrow, 'env>,
+    T::JavaType: TypedArrayElement<'borrow, 'env>,
+{
+    const JNI_SIGNATURE: &'static str = <Array<T> as TryIntoJava<'borrow, 'env>>::JNI_SIGNATURE;
 
-        env.set_byte_array_region(array, 0, data)
-            .expect("Failed to copy bytes to Java array");
+    type JavaType = AutoLocal<'env, 'borrow>;
 
-        env.auto_local(JObject::from(array))
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        Array::new(self).try_into_java(env)
     }
 }
 
-macro_rules! impl_into_java_for_array {
-    ($element_type:ty) => {
-        impl_into_java_for_array!(
-            $element_type,
-             0  1  2  3  4  5  6  7  8  9
-            10 11 12 13 14 15 16 17 18 19
-            20 21 22 23 24 25 26 27 28 29
-            30 31 32 33 34 35 36 37 38 39
-        );
-    };
-
-    ($element_type:ty, $( $count:tt )*) => {
-        $(
-            impl<'borrow, 'env: 'borrow> IntoJava<'borrow, 'env> for [$element_type; $count] {
-                const JNI_SIGNATURE: &'static str = "[B";
+impl<'borrow, 'env, T, const LENGTH: usize> TryIntoJava<'borrow, 'env> for [T; LENGTH]
+where
+    'env: 'borrow,
+    T: TryIntoJava<'borrow, 'env>,
+    T::JavaType: TypedArrayElement<'borrow, 'env>,
+{
+    const JNI_SIGNATURE: &'static str = <Array<T> as TryIntoJava<'borrow, 'env>>::JNI_SIGNATURE;
 
-                type JavaType = AutoLocal<'env, 'borrow>;
+    type JavaType = AutoLocal<'env, 'borrow>;
 
-                fn into_java(self, env: &'borrow JnixEnv<'env>) -> Self::JavaType {
-                    (&self as &[$element_type]).into_java(env)
-                }
-            }
-        )*
-    };
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        Array::new(self).try_into_java(env)
+    }
 }
 
-impl_into_java_for_array!(u8);
+impl<'borrow, 'env, T> TryIntoJava<'borrow, 'env> for Option<T>
+where
+    'env: 'borrow,
+    T: TryIntoJava<'borrow, 'env>,
+    T::JavaType: Boxable<'borrow, 'env>,
+{
+    const JNI_SIGNATURE: &'static str = <Boxed<T> as TryIntoJava<'borrow, 'env>>::JNI_SIGNATURE;
+
+    type JavaType = AutoLocal<'env, 'borrow>;
+
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        match self {
+            Some(t) => Boxed(t).try_into_java(env),
+            None => Ok(env.auto_local(JObject::null())),
+        }
+    }
+}
 
-impl<'borrow, 'env, T> IntoJava<'borrow, 'env> for Option<T>
+impl<'borrow, 'env, T, E> TryIntoJava<'borrow, 'env> for Result<T, E>
 where
     'env: 'borrow,
-    T: IntoJava<'borrow, 'env, JavaType = AutoLocal<'env, 'borrow>>,
+    T: TryIntoJava<'borrow, 'env>,
+    E: IntoJavaException,
 {
     const JNI_SIGNATURE: &'static str = T::JNI_SIGNATURE;
 
-    type JavaType = AutoLocal<'env, 'borrow>;
+    type JavaType = T::JavaType;
 
-    fn into_java(self, env: &'borrow JnixEnv<'env>) -> Self::JavaType {
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
         match self {
-            Some(t) => t.into_java(env),
-            None => env.auto_local(JObject::null()),
+            Ok(value) => value.try_into_java(env),
+            Err(error) => Err(Error::Exception {
+                class_name: error.exception_class_name(),
+                message: error.exception_message(),
+            }),
         }
     }
 }
 
-impl<'borrow, 'env, T> IntoJava<'borrow, 'env> for Vec<T>
+// A `Vec` always becomes a `java.util.ArrayList`, even if its elements are primitive values, so that
+// changing the element type doesn't change the Java type of the field. A `Vec` of primitive values
+// can be sent as a primitive Java array by converting `vec.as_slice()` or `vec.into_boxed_slice()`,
+// or by marking the field with `#[jnix(array)]`.
+impl<'borrow, 'env, T> TryIntoJava<'borrow, 'env> for Vec<T>
 where
     'env: 'borrow,
-    T: IntoJava<'borrow, 'env, JavaType = AutoLocal<'env, 'borrow>>,
+    T: TryIntoJava<'borrow, 'env>,
+    T::JavaType: Boxable<'borrow, 'env>,
 {
     const JNI_SIGNATURE: &'static str = "Ljava/util/ArrayList;";
 
     type JavaType = AutoLocal<'env, 'borrow>;
 
-    fn into_java(self, env: &'borrow JnixEnv<'env>) -> Self::JavaType {
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
         let initial_capacity = self.len();
         let parameters = [JValue::Int(initial_capacity as jint)];
 
-        let class = env.get_class("java/util/ArrayList");
-        let list_object = env
-            .new_object(&class, "(I)V", &parameters)
-            .expect("Failed to create ArrayList object");
+        let class = env.try_get_class("java/util/ArrayList")?;
+        let constructor = env.get_cached_method_id("java/util/ArrayList", "<init>", "(I)V")?;
+        let add_method =
+            env.get_cached_method_id("java/util/ArrayList", "add", "(Ljava/lang/Object;)Z")?;
 
-        let list =
-            JList::from_env(env, list_object).expect("Failed to create JList from ArrayList");
+        let list_object = env
+            .new_object_unchecked(&class, constructor, &parameters)
+            .map_err(|error| env.convert_jni_error(error))?;
+        let list = env.auto_local(list_object);
 
         for element in self {
-            list.add(element.into_java(env).as_obj())
-                .expect("Failed to add element to ArrayList");
+            let java_element = Boxed(element).try_into_java(env)?;
+
+            env.call_method_unchecked(
+                list.as_obj(),
+                add_method,
+                JavaType::Primitive(Primitive::Boolean),
+                &[java_element.as_jvalue()],
+            )
+            .map_err(|error| env.convert_jni_error(error))?;
         }
 
-        env.auto_local(list_object)
+        Ok(list)
+    }
+}
+
+impl<'borrow, 'env, T> TryIntoJava<'borrow, 'env> for Box<T>
+where
+    'env: 'borrow,
+    T: TryIntoJava<'borrow, 'env>,
+{
+    const JNI_SIGNATURE: &'static str = T::JNI_SIGNATURE;
+
+    type JavaType = T::JavaType;
+
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        (*self).try_into_java(env)
+    }
+}
+
+macro_rules! impl_into_java_for_shared_pointers {
+    ( $( $pointer:ident ),* $(,)* ) => {
+        $(
+            impl<'borrow, 'env, T> TryIntoJava<'borrow, 'env> for $pointer<T>
+            where
+                'env: 'borrow,
+                T: TryIntoJava<'borrow, 'env> + Clone,
+            {
+                const JNI_SIGNATURE: &'static str = T::JNI_SIGNATURE;
+
+                type JavaType = T::JavaType;
+
+                fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+                    // The value is only cloned if it is still shared.
+                    $pointer::try_unwrap(self)
+                        .unwrap_or_else(|shared| T::clone(&shared))
+                        .try_into_java(env)
+                }
+            }
+        )*
+    };
+}
+
+impl_into_java_for_shared_pointers!(Rc, Arc);
+
+impl<'borrow, 'env, T> TryIntoJava<'borrow, 'env> for &'_ T
+where
+    'env: 'borrow,
+    T: TryIntoJava<'borrow, 'env> + Clone,
+{
+    const JNI_SIGNATURE: &'static str = T::JNI_SIGNATURE;
+
+    type JavaType = T::JavaType;
+
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        self.clone().try_into_java(env)
     }
 }
 
-impl<'borrow, 'env: 'borrow> IntoJava<'borrow, 'env> for String {
+impl<'borrow, 'env: 'borrow> TryIntoJava<'borrow, 'env> for &'_ str {
     const JNI_SIGNATURE: &'static str = "Ljava/lang/String;";
 
     type JavaType = AutoLocal<'env, 'borrow>;
 
-    fn into_java(self, env: &'borrow JnixEnv<'env>) -> Self::JavaType {
-        let jstring = env.new_string(&self).expect("Failed to create Java String");
-
-        env.auto_local(jstring.into())
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        new_java_string(env, self)
     }
 }
 
-fn ipvx_addr_into_java<'borrow, 'env: 'borrow>(
-    original_octets: &[u8],
-    env: &'borrow JnixEnv<'env>,
-) -> AutoLocal<'env, 'borrow> {
-    let constructor = env
-        .get_static_method_id(
-            "java/net/InetAddress",
-            "getByAddress",
-            "([B)Ljava/net/InetAddress;",
-        )
-        .expect("Failed to get InetAddress.getByAddress method ID");
+macro_rules! impl_into_java_for_strings {
+    ( $( $string_type:ty ),* $(,)* ) => {
+        $(
+            impl<'borrow, 'env: 'borrow> TryIntoJava<'borrow, 'env> for $string_type {
+                const JNI_SIGNATURE: &'static str = "Ljava/lang/String;";
+
+                type JavaType = AutoLocal<'env, 'borrow>;
+
+                fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+                    (&*self).try_into_java(env)
+                }
+            }
+        )*
+    };
+}
 
-    let octets_array = env
-        .new_byte_array(original_octets.len() as i32)
-        .expect("Failed to create byte array to store IP address");
+impl_into_java_for_strings!(String, Cow<'_, str>, Box<str>, Rc<str>, Arc<str>);
 
-    let octet_data: Vec<i8> = original_octets
-        .into_iter()
-        .map(|octet| *octet as i8)
-        .collect();
+impl<'borrow, 'env: 'borrow> TryIntoJava<'borrow, 'env> for &'_ Path {
+    const JNI_SIGNATURE: &'static str = "Ljava/io/File;";
 
-    env.set_byte_array_region(octets_array, 0, &octet_data)
-        .expect("Failed to copy IP address octets to byte array");
+    type JavaType = AutoLocal<'env, 'borrow>;
+
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        JavaFile::new(self, OsStrPolicy::Strict).try_into_java(env)
+    }
+}
+
+impl<'borrow, 'env: 'borrow> TryIntoJava<'borrow, 'env> for PathBuf {
+    const JNI_SIGNATURE: &'static str = "Ljava/io/File;";
+
+    type JavaType = AutoLocal<'env, 'borrow>;
 
-    let octets = env.auto_local(JObject::from(octets_array));
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        self.as_path().try_into_java(env)
+    }
+}
+
+fn ipvx_addr_into_java<'borrow, 'env: 'borrow>(
+    original_octets: &[u8],
+    env: &'borrow JnixEnv<'env>,
+) -> Result<AutoLocal<'env, 'borrow>, Error> {
+    let class = env.try_get_class("java/net/InetAddress")?;
+    let constructor = env.get_cached_static_method_id(
+        "java/net/InetAddress",
+        "getByAddress",
+        "([B)Ljava/net/InetAddress;",
+    )?;
+
+    let octets = original_octets.try_into_java(env)?;
     let result = env
         .call_static_method_unchecked(
-            "java/net/InetAddress",
+            &class,
             constructor,
             JavaType::Object("java/net/InetAddress".to_owned()),
             &[octets.as_jvalue()],
         )
-        .expect("Failed to create InetAddress Java object");
+        .map_err(|error| env.convert_jni_error(error))?;
 
     match result {
-        JValue::Object(object) => env.auto_local(object),
-        value => {
-            panic!(
-                "InetAddress.getByAddress returned an invalid value: {:?}",
-                value
-            );
-        }
+        JValue::Object(object) => Ok(env.auto_local(object)),
+        value => Err(Error::InvalidReturnValue {
+            source: "InetAddress.getByAddress".to_owned(),
+            value_type: value.type_name(),
+        }),
     }
 }
 
-impl<'borrow, 'env: 'borrow> IntoJava<'borrow, 'env> for Ipv4Addr {
+impl<'borrow, 'env: 'borrow> TryIntoJava<'borrow, 'env> for Ipv4Addr {
     const JNI_SIGNATURE: &'static str = "Ljava/net/InetAddress;";
 
     type JavaType = AutoLocal<'env, 'borrow>;
 
-    fn into_java(self, env: &'borrow JnixEnv<'env>) -> Self::JavaType {
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
         ipvx_addr_into_java(self.octets().as_ref(), env)
     }
 }
 
-impl<'borrow, 'env: 'borrow> IntoJava<'borrow, 'env> for Ipv6Addr {
+impl<'borrow, 'env: 'borrow> TryIntoJava<'borrow, 'env> for Ipv6Addr {
     const JNI_SIGNATURE: &'static str = "Ljava/net/InetAddress;";
 
     type JavaType = AutoLocal<'env, 'borrow>;
 
-    fn into_java(self, env: &'borrow JnixEnv<'env>) -> Self::JavaType {
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
         ipvx_addr_into_java(self.octets().as_ref(), env)
     }
 }
 
-impl<'borrow, 'env: 'borrow> IntoJava<'borrow, 'env> for IpAddr {
+impl<'borrow, 'env: 'borrow> TryIntoJava<'borrow, 'env> for IpAddr {
     const JNI_SIGNATURE: &'static str = "Ljava/net/InetAddress;";
 
     type JavaType = AutoLocal<'env, 'borrow>;
 
-    fn into_java(self, env: &'borrow JnixEnv<'env>) -> Self::JavaType {
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
         match self {
-            IpAddr::V4(address) => address.into_java(env),
-            IpAddr::V6(address) => address.into_java(env),
+            IpAddr::V4(address) => address.try_into_java(env),
+            IpAddr::V6(address) => address.try_into_java(env),
         }
     }
 }
 
-impl<'borrow, 'env: 'borrow> IntoJava<'borrow, 'env> for SocketAddr {
+impl<'borrow, 'env: 'borrow> TryIntoJava<'borrow, 'env> for SocketAddr {
     const JNI_SIGNATURE: &'static str = "Ljava/net/InetSocketAddress;";
 
     type JavaType = AutoLocal<'env, 'borrow>;
 
-    fn into_java(self, env: &'borrow JnixEnv<'env>) -> Self::JavaType {
-        let ip_address = self.ip().into_java(env);
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        let ip_address = self.ip().try_into_java(env)?;
         let port = self.port() as jint;
         let parameters = [JValue::Object(ip_address.as_obj()), JValue::Int(port)];
 
-        let class = env.get_class("java/net/InetSocketAddress");
+        let class = env.try_get_class("java/net/InetSocketAddress")?;
+        let constructor = env.get_cached_method_id(
+            "java/net/InetSocketAddress",
+            "<init>",
+            "(Ljava/net/InetAddress;I)V",
+        )?;
         let object = env
-            .new_object(&class, "(Ljava/net/InetAddress;I)V", &parameters)
-            .expect("Failed to convert SocketAddr Rust type into InetSocketAddress Java object");
+            .new_object_unchecked(&class, constructor, &parameters)
+            .map_err(|error| env.convert_jni_error(error))?;
 
-        env.auto_local(object)
+        Ok(env.auto_local(object))
     }
 }
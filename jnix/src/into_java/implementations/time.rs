@@ -0,0 +1,165 @@
+use crate::{
+    time::{new_duration, new_instant},
+    Error, JnixEnv, TryIntoJava,
+};
+use jni::{objects::AutoLocal, sys::jlong};
+use std::{
+    convert::TryFrom,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+impl<'borrow, 'env: 'borrow> TryIntoJava<'borrow, 'env> for Duration {
+    const JNI_SIGNATURE: &'static str = "Ljava/time/Duration;";
+
+    type JavaType = AutoLocal<'env, 'borrow>;
+
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        let seconds = jlong::try_from(self.as_secs()).map_err(|_| Error::UnrepresentableValue {
+            value: format!("{:?}", self),
+            java_type: "java.time.Duration",
+        })?;
+
+        new_duration(seconds, jlong::from(self.subsec_nanos()), env)
+    }
+}
+
+impl<'borrow, 'env: 'borrow> TryIntoJava<'borrow, 'env> for SystemTime {
+    const JNI_SIGNATURE: &'static str = "Ljava/time/Instant;";
+
+    type JavaType = AutoLocal<'env, 'borrow>;
+
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        let unrepresentable = || Error::UnrepresentableValue {
+            value: format!("{:?}", self),
+            java_type: "java.time.Instant",
+        };
+
+        let (seconds, nanos) = match self.duration_since(UNIX_EPOCH) {
+            Ok(since_epoch) => (
+                jlong::try_from(since_epoch.as_secs()).map_err(|_| unrepresentable())?,
+                jlong::from(since_epoch.subsec_nanos()),
+            ),
+            Err(error) => {
+                let before_epoch = error.duration();
+
+                (
+                    -jlong::try_from(before_epoch.as_secs()).map_err(|_| unrepresentable())?,
+                    -jlong::from(before_epoch.subsec_nanos()),
+                )
+            }
+        };
+
+        new_instant(seconds, nanos, env)
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_types {
+    use crate::{
+        time::{new_instant, new_local_date, new_zoned_date_time},
+        Error, JnixEnv, TryIntoJava,
+    };
+    use ::chrono::{DateTime, Datelike, FixedOffset, NaiveDate, Utc};
+    use jni::{
+        objects::AutoLocal,
+        sys::{jint, jlong},
+    };
+
+    impl<'borrow, 'env: 'borrow> TryIntoJava<'borrow, 'env> for DateTime<Utc> {
+        const JNI_SIGNATURE: &'static str = "Ljava/time/Instant;";
+
+        type JavaType = AutoLocal<'env, 'borrow>;
+
+        fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+            new_instant(
+                self.timestamp(),
+                jlong::from(self.timestamp_subsec_nanos()),
+                env,
+            )
+        }
+    }
+
+    impl<'borrow, 'env: 'borrow> TryIntoJava<'borrow, 'env> for DateTime<FixedOffset> {
+        const JNI_SIGNATURE: &'static str = "Ljava/time/ZonedDateTime;";
+
+        type JavaType = AutoLocal<'env, 'borrow>;
+
+        fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+            let offset = self.offset().local_minus_utc();
+
+            new_zoned_date_time(
+                self.timestamp(),
+                jlong::from(self.timestamp_subsec_nanos()),
+                offset,
+                env,
+            )
+        }
+    }
+
+    impl<'borrow, 'env: 'borrow> TryIntoJava<'borrow, 'env> for NaiveDate {
+        const JNI_SIGNATURE: &'static str = "Ljava/time/LocalDate;";
+
+        type JavaType = AutoLocal<'env, 'borrow>;
+
+        fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+            new_local_date(self.year(), self.month() as jint, self.day() as jint, env)
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_types {
+    use crate::{
+        time::{new_duration, new_local_date, new_zoned_date_time},
+        Error, JnixEnv, TryIntoJava,
+    };
+    use ::time::{Date, Duration, OffsetDateTime};
+    use jni::{
+        objects::AutoLocal,
+        sys::{jint, jlong},
+    };
+
+    impl<'borrow, 'env: 'borrow> TryIntoJava<'borrow, 'env> for Duration {
+        const JNI_SIGNATURE: &'static str = "Ljava/time/Duration;";
+
+        type JavaType = AutoLocal<'env, 'borrow>;
+
+        fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+            new_duration(
+                self.whole_seconds(),
+                jlong::from(self.subsec_nanoseconds()),
+                env,
+            )
+        }
+    }
+
+    impl<'borrow, 'env: 'borrow> TryIntoJava<'borrow, 'env> for OffsetDateTime {
+        const JNI_SIGNATURE: &'static str = "Ljava/time/ZonedDateTime;";
+
+        type JavaType = AutoLocal<'env, 'borrow>;
+
+        fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+            new_zoned_date_time(
+                self.unix_timestamp(),
+                jlong::from(self.nanosecond()),
+                self.offset().whole_seconds(),
+                env,
+            )
+        }
+    }
+
+    impl<'borrow, 'env: 'borrow> TryIntoJava<'borrow, 'env> for Date {
+        const JNI_SIGNATURE: &'static str = "Ljava/time/LocalDate;";
+
+        type JavaType = AutoLocal<'env, 'borrow>;
+
+        fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+            new_local_date(
+                self.year(),
+                jint::from(u8::from(self.month())),
+                jint::from(self.day()),
+                env,
+            )
+        }
+    }
+}
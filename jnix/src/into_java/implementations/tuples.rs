@@ -0,0 +1,91 @@
+use crate::{
+    tuples::{TupleClass, TupleElements},
+    Error, JnixEnv, TryIntoJava,
+};
+use jni::{
+    objects::{AutoLocal, JObject},
+    sys::{jobjectArray, jsize},
+};
+
+impl<'borrow, 'env, A, B> TryIntoJava<'borrow, 'env> for (A, B)
+where
+    'env: 'borrow,
+    (A, B): TupleElements<'borrow, 'env>,
+{
+    const JNI_SIGNATURE: &'static str = "Lkotlin/Pair;";
+
+    type JavaType = AutoLocal<'env, 'borrow>;
+
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        TupleClass::new("kotlin/Pair", self).try_into_java(env)
+    }
+}
+
+impl<'borrow, 'env, A, B, C> TryIntoJava<'borrow, 'env> for (A, B, C)
+where
+    'env: 'borrow,
+    (A, B, C): TupleElements<'borrow, 'env>,
+{
+    const JNI_SIGNATURE: &'static str = "Lkotlin/Triple;";
+
+    type JavaType = AutoLocal<'env, 'borrow>;
+
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        TupleClass::new("kotlin/Triple", self).try_into_java(env)
+    }
+}
+
+macro_rules! impl_into_java_for_object_array_tuples {
+    ( $( ( $( $element_type:ident ),* ) )* ) => {
+        $(
+            impl<'borrow, 'env, $( $element_type ),*> TryIntoJava<'borrow, 'env>
+                for ( $( $element_type, )* )
+            where
+                'env: 'borrow,
+                ( $( $element_type, )* ): TupleElements<'borrow, 'env>,
+            {
+                const JNI_SIGNATURE: &'static str = "[Ljava/lang/Object;";
+
+                type JavaType = AutoLocal<'env, 'borrow>;
+
+                fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+                    object_array_from_elements(self.into_java_elements(env)?, env)
+                }
+            }
+        )*
+    };
+}
+
+impl_into_java_for_object_array_tuples! {
+    (A, B, C, D)
+    (A, B, C, D, E)
+    (A, B, C, D, E, F)
+    (A, B, C, D, E, F, G)
+    (A, B, C, D, E, F, G, H)
+    (A, B, C, D, E, F, G, H, I)
+    (A, B, C, D, E, F, G, H, I, J)
+    (A, B, C, D, E, F, G, H, I, J, K)
+    (A, B, C, D, E, F, G, H, I, J, K, L)
+}
+
+fn object_array_from_elements<'borrow, 'env: 'borrow>(
+    elements: Vec<AutoLocal<'env, 'borrow>>,
+    env: &'borrow JnixEnv<'env>,
+) -> Result<AutoLocal<'env, 'borrow>, Error> {
+    let object_class = env.try_get_class("java/lang/Object")?;
+    let array = env
+        .new_object_array(elements.len() as jsize, &object_class, JObject::null())
+        .map_err(|error| env.convert_jni_error(error))?;
+    let array = env.auto_local(JObject::from(array));
+
+    for (index, element) in elements.iter().enumerate() {
+        env.set_object_array_element(
+            array.as_obj().into_inner() as jobjectArray,
+            index as jsize,
+            element.as_obj(),
+        )
+        .map_err(|error| env.convert_jni_error(error))?;
+    }
+
+    Ok(array)
+}
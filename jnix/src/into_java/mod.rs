@@ -1,6 +1,18 @@
+mod elements;
 mod implementations;
 
-use crate::{AsJValue, JnixEnv};
+pub use self::elements::{ArrayElement, SliceElement, TypedArrayElement};
+
+use crate::{AsJValue, Error, JnixEnv};
+use std::any::type_name;
+
+pub trait TryIntoJava<'borrow, 'env: 'borrow> {
+    const JNI_SIGNATURE: &'static str;
+
+    type JavaType: AsJValue<'env>;
+
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error>;
+}
 
 pub trait IntoJava<'borrow, 'env: 'borrow> {
     const JNI_SIGNATURE: &'static str;
@@ -13,3 +25,23 @@ pub trait IntoJava<'borrow, 'env: 'borrow> {
         Self::JNI_SIGNATURE
     }
 }
+
+impl<'borrow, 'env, T> IntoJava<'borrow, 'env> for T
+where
+    'env: 'borrow,
+    T: TryIntoJava<'borrow, 'env>,
+{
+    const JNI_SIGNATURE: &'static str = T::JNI_SIGNATURE;
+
+    type JavaType = T::JavaType;
+
+    fn into_java(self, env: &'borrow JnixEnv<'env>) -> Self::JavaType {
+        self.try_into_java(env).unwrap_or_else(|error| {
+            panic!(
+                "Failed to convert {} Rust value into a Java object: {}",
+                type_name::<T>(),
+                error
+            )
+        })
+    }
+}
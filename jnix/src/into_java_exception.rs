@@ -0,0 +1,85 @@
+use crate::{Error, JnixEnv};
+use jni::{
+    objects::{AutoLocal, GlobalRef, JObject},
+    sys::{jboolean, jbyte, jchar, jdouble, jfloat, jint, jlong, jshort},
+};
+use std::borrow::Cow;
+
+/// Rust errors that can be thrown as Java exceptions.
+pub trait IntoJavaException {
+    /// The name of the Java exception class to throw, in JNI format (e.g.
+    /// `java/lang/IllegalStateException`).
+    fn exception_class_name(&self) -> Cow<'static, str>;
+
+    /// The message to use when constructing the Java exception.
+    fn exception_message(&self) -> String;
+
+    /// An existing Java exception object to throw instead of constructing a new one, like an
+    /// exception that was caught earlier.
+    fn exception_object(&self) -> Option<&GlobalRef> {
+        None
+    }
+}
+
+impl IntoJavaException for Error {
+    fn exception_class_name(&self) -> Cow<'static, str> {
+        match self {
+            Error::JavaException(exception) => Cow::Owned(exception.class_name.replace(".", "/")),
+            Error::Exception { class_name, .. } => class_name.clone(),
+            _ => Cow::Borrowed("java/lang/RuntimeException"),
+        }
+    }
+
+    fn exception_message(&self) -> String {
+        match self {
+            Error::JavaException(exception) => exception.message.clone().unwrap_or_default(),
+            Error::Exception { message, .. } => message.clone(),
+            error => error.to_string(),
+        }
+    }
+
+    fn exception_object(&self) -> Option<&GlobalRef> {
+        match self {
+            Error::JavaException(exception) => exception.throwable(),
+            _ => None,
+        }
+    }
+}
+
+/// Value returned from a native method when an exception has been thrown instead.
+///
+/// The JVM ignores the returned value when an exception is pending, so this is just a null or zero
+/// value of the expected type.
+pub trait NullValue<'borrow, 'env: 'borrow> {
+    fn null_value(env: &'borrow JnixEnv<'env>) -> Self;
+}
+
+impl<'borrow, 'env: 'borrow> NullValue<'borrow, 'env> for AutoLocal<'env, 'borrow> {
+    fn null_value(env: &'borrow JnixEnv<'env>) -> Self {
+        env.auto_local(JObject::null())
+    }
+}
+
+macro_rules! impl_for_primitives {
+    ( $( $primitive:ty ),* $(,)* ) => {
+        $(
+            impl<'borrow, 'env: 'borrow> NullValue<'borrow, 'env> for $primitive {
+                fn null_value(_: &'borrow JnixEnv<'env>) -> Self {
+                    Self::default()
+                }
+            }
+        )*
+    };
+}
+
+impl_for_primitives!(
+    (),
+    jboolean,
+    jbyte,
+    jchar,
+    jshort,
+    jint,
+    jlong,
+    jfloat,
+    jdouble
+);
@@ -0,0 +1,240 @@
+use crate::{FromJava, JnixEnv};
+use jni::{
+    errors::Result as JniResult,
+    objects::{GlobalRef, JObject},
+};
+use std::{error::Error as StdError, fmt};
+
+/// Maximum number of chained causes that are captured from a Java exception.
+const MAX_CAUSE_DEPTH: usize = 16;
+
+/// A Java exception that was thrown and cleared, captured as a Rust value.
+///
+/// A reference to the original exception object is kept, so that it can be thrown again with its
+/// stack trace and cause intact.
+#[derive(Clone)]
+pub struct JavaException {
+    pub class_name: String,
+    pub message: Option<String>,
+    pub stack_trace: Vec<StackTraceElement>,
+    pub cause: Option<Box<JavaException>>,
+    throwable: Option<GlobalRef>,
+}
+
+/// A single frame of a captured Java exception's stack trace.
+#[derive(Clone, Debug)]
+pub struct StackTraceElement {
+    pub class_name: String,
+    pub method_name: String,
+    pub file_name: Option<String>,
+    pub line_number: i32,
+}
+
+impl JavaException {
+    pub(crate) fn from_throwable<'env>(env: &JnixEnv<'env>, throwable: JObject<'env>) -> Self {
+        let mut exception = Self::from_throwable_with_depth(env, throwable, 0);
+
+        exception.throwable = env
+            .new_global_ref(throwable)
+            .map_err(|error| Self::clear(env, error))
+            .ok();
+
+        exception
+    }
+
+    /// The original Java exception object, if a reference to it could be kept.
+    pub fn throwable(&self) -> Option<&GlobalRef> {
+        self.throwable.as_ref()
+    }
+
+    fn from_throwable_with_depth<'env>(
+        env: &JnixEnv<'env>,
+        throwable: JObject<'env>,
+        depth: usize,
+    ) -> Self {
+        let class_name = Self::class_name(env, throwable)
+            .unwrap_or_else(|_| "<unknown exception class>".to_owned());
+        let message = Self::message(env, throwable).unwrap_or(None);
+        let stack_trace = Self::stack_trace(env, throwable).unwrap_or_default();
+        let cause = if depth < MAX_CAUSE_DEPTH {
+            Self::cause(env, throwable, depth).unwrap_or(None)
+        } else {
+            None
+        };
+
+        JavaException {
+            class_name,
+            message,
+            stack_trace,
+            cause,
+            throwable: None,
+        }
+    }
+
+    fn class_name<'env>(env: &JnixEnv<'env>, throwable: JObject<'env>) -> JniResult<String> {
+        let class = env.auto_local(
+            env.call_method(throwable, "getClass", "()Ljava/lang/Class;", &[])
+                .and_then(|class| class.l())
+                .map_err(|error| Self::clear(env, error))?,
+        );
+
+        call_string_method(env, class.as_obj(), "getName").map(Option::unwrap_or_default)
+    }
+
+    fn message<'env>(env: &JnixEnv<'env>, throwable: JObject<'env>) -> JniResult<Option<String>> {
+        call_string_method(env, throwable, "getMessage")
+    }
+
+    fn stack_trace<'env>(
+        env: &JnixEnv<'env>,
+        throwable: JObject<'env>,
+    ) -> JniResult<Vec<StackTraceElement>> {
+        let elements = env.auto_local(
+            env.call_method(
+                throwable,
+                "getStackTrace",
+                "()[Ljava/lang/StackTraceElement;",
+                &[],
+            )
+            .and_then(|elements| elements.l())
+            .map_err(|error| Self::clear(env, error))?,
+        );
+
+        let elements_array = elements.as_obj().into_inner();
+        let length = env
+            .get_array_length(elements_array)
+            .map_err(|error| Self::clear(env, error))?;
+        let mut stack_trace = Vec::with_capacity(length as usize);
+
+        for index in 0..length {
+            let element = env.auto_local(
+                env.get_object_array_element(elements_array, index)
+                    .map_err(|error| Self::clear(env, error))?,
+            );
+
+            stack_trace.push(StackTraceElement::from_java(env, element.as_obj())?);
+        }
+
+        Ok(stack_trace)
+    }
+
+    fn cause<'env>(
+        env: &JnixEnv<'env>,
+        throwable: JObject<'env>,
+        depth: usize,
+    ) -> JniResult<Option<Box<JavaException>>> {
+        let cause = env.auto_local(
+            env.call_method(throwable, "getCause", "()Ljava/lang/Throwable;", &[])
+                .and_then(|cause| cause.l())
+                .map_err(|error| Self::clear(env, error))?,
+        );
+
+        if cause.as_obj().is_null() {
+            Ok(None)
+        } else {
+            Ok(Some(Box::new(Self::from_throwable_with_depth(
+                env,
+                cause.as_obj(),
+                depth + 1,
+            ))))
+        }
+    }
+
+    fn clear<E>(env: &JnixEnv, error: E) -> E {
+        if env.exception_check().unwrap_or(false) {
+            let _ = env.exception_clear();
+        }
+
+        error
+    }
+}
+
+impl StackTraceElement {
+    fn from_java<'env>(env: &JnixEnv<'env>, element: JObject<'env>) -> JniResult<Self> {
+        let class_name = call_string_method(env, element, "getClassName")?.unwrap_or_default();
+        let method_name = call_string_method(env, element, "getMethodName")?.unwrap_or_default();
+        let file_name = call_string_method(env, element, "getFileName")?;
+        let line_number = env
+            .call_method(element, "getLineNumber", "()I", &[])
+            .and_then(|line_number| line_number.i())
+            .map_err(|error| JavaException::clear(env, error))?;
+
+        Ok(StackTraceElement {
+            class_name,
+            method_name,
+            file_name,
+            line_number,
+        })
+    }
+}
+
+fn call_string_method<'env>(
+    env: &JnixEnv<'env>,
+    object: JObject<'env>,
+    method_name: &str,
+) -> JniResult<Option<String>> {
+    let string = env.auto_local(
+        env.call_method(object, method_name, "()Ljava/lang/String;", &[])
+            .and_then(|string| string.l())
+            .map_err(|error| JavaException::clear(env, error))?,
+    );
+
+    Ok(<Option<String>>::from_java(env, string.as_obj()))
+}
+
+impl fmt::Display for JavaException {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}", self.class_name)?;
+
+        if let Some(message) = &self.message {
+            write!(formatter, ": {}", message)?;
+        }
+
+        for element in &self.stack_trace {
+            write!(formatter, "\n\tat {}", element)?;
+        }
+
+        if let Some(cause) = &self.cause {
+            write!(formatter, "\nCaused by: {}", cause)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for JavaException {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("JavaException")
+            .field("class_name", &self.class_name)
+            .field("message", &self.message)
+            .field("stack_trace", &self.stack_trace)
+            .field("cause", &self.cause)
+            .finish()
+    }
+}
+
+impl StdError for JavaException {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.cause
+            .as_ref()
+            .map(|cause| cause.as_ref() as &(dyn StdError + 'static))
+    }
+}
+
+impl fmt::Display for StackTraceElement {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}.{}(", self.class_name, self.method_name)?;
+
+        match &self.file_name {
+            Some(file_name) if self.line_number >= 0 => {
+                write!(formatter, "{}:{}", file_name, self.line_number)?
+            }
+            Some(file_name) => write!(formatter, "{}", file_name)?,
+            None if self.line_number == -2 => write!(formatter, "Native Method")?,
+            None => write!(formatter, "Unknown Source")?,
+        }
+
+        write!(formatter, ")")
+    }
+}
@@ -0,0 +1,67 @@
+//! Conversions between Rust strings and Java strings through UTF-16.
+//!
+//! The JNI functions that work with UTF-8 strings actually use Java's modified UTF-8, which
+//! encodes supplementary characters as surrogate pairs and embedded NULs as two bytes. Going
+//! through UTF-16 instead preserves every character exactly.
+
+use crate::{Error, JnixEnv};
+use jni::{
+    errors::ErrorKind as JniErrorKind,
+    objects::{AutoLocal, JObject},
+    sys::{jchar, jsize, jstring},
+};
+
+/// Creates a Java string with the same characters as the Rust string.
+pub(crate) fn new_java_string<'borrow, 'env: 'borrow>(
+    env: &'borrow JnixEnv<'env>,
+    string: &str,
+) -> Result<AutoLocal<'env, 'borrow>, Error> {
+    let code_units: Vec<jchar> = string.encode_utf16().collect();
+    let raw_env = env.get_native_interface();
+
+    let new_string = unsafe { (**raw_env).NewString }
+        .ok_or_else(|| Error::Jni(JniErrorKind::JNIEnvMethodNotFound("NewString").into()))?;
+    let java_string =
+        unsafe { new_string(raw_env, code_units.as_ptr(), code_units.len() as jsize) };
+
+    if java_string.is_null() {
+        return Err(env.convert_jni_error(JniErrorKind::JavaException.into()));
+    }
+
+    Ok(env.auto_local(JObject::from(java_string)))
+}
+
+/// Reads the characters of a Java string into a Rust string.
+///
+/// Java strings may contain unpaired surrogates, which can't be represented in a Rust string. They
+/// are replaced with `U+FFFD REPLACEMENT CHARACTER`. A `null` reference is an error, because
+/// passing it to the JNI string functions would crash the JVM.
+pub(crate) fn java_string_to_string(env: &JnixEnv, string: JObject) -> Result<String, Error> {
+    if string.is_null() {
+        return Err(Error::Jni(
+            JniErrorKind::NullPtr("Java string to convert into a Rust string").into(),
+        ));
+    }
+
+    let raw_env = env.get_native_interface();
+    let java_string = string.into_inner() as jstring;
+
+    let get_string_length = unsafe { (**raw_env).GetStringLength }
+        .ok_or_else(|| Error::Jni(JniErrorKind::JNIEnvMethodNotFound("GetStringLength").into()))?;
+    let get_string_region = unsafe { (**raw_env).GetStringRegion }
+        .ok_or_else(|| Error::Jni(JniErrorKind::JNIEnvMethodNotFound("GetStringRegion").into()))?;
+
+    let length = unsafe { get_string_length(raw_env, java_string) };
+    let mut code_units: Vec<jchar> = vec![0; length as usize];
+
+    unsafe { get_string_region(raw_env, java_string, 0, length, code_units.as_mut_ptr()) };
+
+    if env
+        .exception_check()
+        .map_err(|error| env.convert_jni_error(error))?
+    {
+        return Err(env.convert_jni_error(JniErrorKind::JavaException.into()));
+    }
+
+    Ok(String::from_utf16_lossy(&code_units))
+}
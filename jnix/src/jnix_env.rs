@@ -1,21 +1,32 @@
+use crate::{
+    class_cache::{ClassCache, JavaVmCaches, MemberKind, CLASS_CACHES},
+    Error, IntoJavaException, JavaException, NullValue, TryIntoJava,
+};
 use jni::{
-    objects::{GlobalRef, JObject},
+    errors::{Error as JniError, ErrorKind as JniErrorKind},
+    objects::{
+        AutoLocal, GlobalRef, JFieldID, JMethodID, JObject, JStaticFieldID, JStaticMethodID,
+        JThrowable, JValue,
+    },
+    signature::JavaType,
     JNIEnv,
 };
-use once_cell::sync::Lazy;
-use parking_lot::Mutex;
-use std::{borrow::Cow, collections::HashMap, ops::Deref};
-
-static CLASS_CACHE: Lazy<Mutex<HashMap<String, GlobalRef>>> =
-    Lazy::new(|| Mutex::new(HashMap::new()));
+use once_cell::unsync::OnceCell;
+use std::{borrow::Cow, ops::Deref};
 
 pub struct JnixEnv<'env> {
     env: JNIEnv<'env>,
+    java_vm_address: OnceCell<usize>,
+    class_loader: Option<GlobalRef>,
 }
 
 impl<'env> From<JNIEnv<'env>> for JnixEnv<'env> {
     fn from(env: JNIEnv<'env>) -> Self {
-        JnixEnv { env }
+        JnixEnv {
+            env,
+            java_vm_address: OnceCell::new(),
+            class_loader: None,
+        }
     }
 }
 
@@ -28,43 +39,486 @@ impl<'env> Deref for JnixEnv<'env> {
 }
 
 impl<'env> JnixEnv<'env> {
+    /// Uses a `ClassLoader` to load all classes used through this environment.
+    ///
+    /// The classes are cached separately from the ones loaded with other class loaders, so classes
+    /// with the same name from different class loaders don't replace each other.
+    pub fn with_class_loader(mut self, class_loader: JObject<'env>) -> Result<Self, Error> {
+        let class_loader = self
+            .env
+            .new_global_ref(class_loader)
+            .map_err(|error| self.convert_jni_error(error))?;
+
+        self.class_loader = Some(class_loader);
+
+        Ok(self)
+    }
+
     pub fn get_class<'a>(&self, class_name: impl Into<Cow<'a, str>>) -> GlobalRef {
+        self.try_get_class(class_name)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Returns a class, loading it if it isn't cached yet.
+    ///
+    /// Classes are cached by name for each `JavaVM` and class loader. They are loaded with the
+    /// class loader of this environment if it has one, or else with `FindClass` and then with the
+    /// registered class loader.
+    pub fn try_get_class<'a>(
+        &self,
+        class_name: impl Into<Cow<'a, str>>,
+    ) -> Result<GlobalRef, Error> {
         let class_name = class_name.into();
-        let mut cache = CLASS_CACHE.lock();
         log::debug!("JnixEnv::get_class({})", class_name);
 
-        if let Some(class) = cache.get(class_name.as_ref()) {
-            class.clone()
-        } else {
-            let class = self.load_class(class_name.as_ref());
+        let (cached_class, class_loader) = self.with_class_cache(|cache| {
+            let cached_class = cache.classes.get(class_name.as_ref()).cloned();
+
+            (cached_class, cache.class_loader.clone())
+        })?;
+
+        if let Some(class) = cached_class {
+            return Ok(class);
+        }
+
+        // The cache must not be locked while the class is loaded, because that can run its static
+        // initializer, which can call back into native code that also uses the cache.
+        let class = self.load_class(class_name.as_ref(), class_loader.as_ref())?;
+
+        self.with_class_cache(|cache| {
+            cache
+                .classes
+                .entry(class_name.into_owned())
+                .or_insert(class)
+                .clone()
+        })
+    }
+
+    /// Removes a class and its cached member IDs from the caches of every class loader in the
+    /// current `JavaVM`.
+    ///
+    /// Returns `true` if the class was cached.
+    pub fn remove_cached_class(&self, class_name: &str) -> Result<bool, Error> {
+        self.with_java_vm_caches(|caches| caches.remove_class(class_name))
+    }
+
+    /// Removes all classes and member IDs from the caches of every class loader in the current
+    /// `JavaVM`.
+    ///
+    /// The registered class loader is kept.
+    pub fn clear_class_cache(&self) -> Result<(), Error> {
+        self.with_java_vm_caches(JavaVmCaches::clear)
+    }
+
+    /// Runs an action on the class cache of this environment's class loader in the current
+    /// `JavaVM`.
+    ///
+    /// The cache is locked while the action runs, so it must not call into the JVM.
+    fn with_class_cache<R>(&self, action: impl FnOnce(&mut ClassCache) -> R) -> Result<R, Error> {
+        self.with_java_vm_caches(|caches| {
+            action(caches.cache_for(&self.env, self.class_loader.as_ref()))
+        })
+    }
 
-            cache.insert(class_name.into_owned(), class.clone());
+    /// Runs an action on the class caches of the current `JavaVM`.
+    ///
+    /// The caches are locked while the action runs, so it must not call into the JVM.
+    fn with_java_vm_caches<R>(
+        &self,
+        action: impl FnOnce(&mut JavaVmCaches) -> R,
+    ) -> Result<R, Error> {
+        let java_vm_address = *self.java_vm_address.get_or_try_init(|| {
+            self.env
+                .get_java_vm()
+                .map(|java_vm| java_vm.get_java_vm_pointer() as usize)
+                .map_err(|error| self.convert_jni_error(error))
+        })?;
+        let mut caches = CLASS_CACHES.lock();
 
-            class
+        Ok(action(caches.entry(java_vm_address).or_default()))
+    }
+
+    /// Returns the ID of an instance method, which is cached together with its class.
+    pub fn get_cached_method_id(
+        &self,
+        class_name: &str,
+        method_name: &str,
+        signature: &str,
+    ) -> Result<JMethodID<'env>, Error> {
+        let id = self.get_member_id(
+            MemberKind::Method,
+            class_name,
+            method_name,
+            signature,
+            |class| {
+                self.env
+                    .get_method_id(class, method_name, signature)
+                    .map(|id| id.into_inner() as usize)
+            },
+        )?;
+
+        Ok(JMethodID::from(id as jni::sys::jmethodID))
+    }
+
+    /// Returns the ID of a static method, which is cached together with its class.
+    pub fn get_cached_static_method_id(
+        &self,
+        class_name: &str,
+        method_name: &str,
+        signature: &str,
+    ) -> Result<JStaticMethodID<'env>, Error> {
+        let id = self.get_member_id(
+            MemberKind::StaticMethod,
+            class_name,
+            method_name,
+            signature,
+            |class| {
+                self.env
+                    .get_static_method_id(class, method_name, signature)
+                    .map(|id| id.into_inner() as usize)
+            },
+        )?;
+
+        Ok(JStaticMethodID::from(id as jni::sys::jmethodID))
+    }
+
+    /// Returns the ID of an instance field, which is cached together with its class.
+    pub fn get_cached_field_id(
+        &self,
+        class_name: &str,
+        field_name: &str,
+        signature: &str,
+    ) -> Result<JFieldID<'env>, Error> {
+        let id = self.get_member_id(
+            MemberKind::Field,
+            class_name,
+            field_name,
+            signature,
+            |class| {
+                self.env
+                    .get_field_id(class, field_name, signature)
+                    .map(|id| id.into_inner() as usize)
+            },
+        )?;
+
+        Ok(JFieldID::from(id as jni::sys::jfieldID))
+    }
+
+    /// Returns the ID of a static field, which is cached together with its class.
+    pub fn get_cached_static_field_id(
+        &self,
+        class_name: &str,
+        field_name: &str,
+        signature: &str,
+    ) -> Result<JStaticFieldID<'env>, Error> {
+        let id = self.get_member_id(
+            MemberKind::StaticField,
+            class_name,
+            field_name,
+            signature,
+            |class| {
+                self.env
+                    .get_static_field_id(class, field_name, signature)
+                    .map(|id| id.into_inner() as usize)
+            },
+        )?;
+
+        Ok(JStaticFieldID::from(id as jni::sys::jfieldID))
+    }
+
+    fn get_member_id(
+        &self,
+        kind: MemberKind,
+        class_name: &str,
+        member_name: &str,
+        signature: &str,
+        lookup: impl FnOnce(&GlobalRef) -> Result<usize, JniError>,
+    ) -> Result<usize, Error> {
+        let not_found = || match kind {
+            MemberKind::Method | MemberKind::StaticMethod => Error::MethodNotFound {
+                class_name: class_name.to_owned(),
+                method_name: member_name.to_owned(),
+                signature: signature.to_owned(),
+            },
+            MemberKind::Field | MemberKind::StaticField => Error::FieldNotFound {
+                class_name: class_name.to_owned(),
+                field_name: member_name.to_owned(),
+                signature: signature.to_owned(),
+            },
+        };
+
+        let cached_id = self.with_class_cache(|cache| {
+            cache.get_member_id(kind, class_name, member_name, signature)
+        })?;
+
+        if let Some(id) = cached_id {
+            return id.ok_or_else(not_found);
         }
+
+        log::debug!(
+            "JnixEnv::get_member_id({:?}, {}, {}, {})",
+            kind,
+            class_name,
+            member_name,
+            signature
+        );
+
+        let class = self.try_get_class(class_name)?;
+        let id = match lookup(&class) {
+            Ok(id) => Some(id),
+            Err(error) => match error.kind() {
+                JniErrorKind::MethodNotFound(..) | JniErrorKind::FieldNotFound(..) => None,
+                _ => match self.convert_jni_error(error) {
+                    Error::JavaException(ref exception)
+                        if exception.class_name == kind.not_found_exception_name() =>
+                    {
+                        None
+                    }
+                    error => return Err(error),
+                },
+            },
+        };
+
+        self.with_class_cache(|cache| {
+            cache.insert_member_id(kind, class_name, member_name, signature, id)
+        })?;
+
+        id.ok_or_else(not_found)
     }
 
     pub fn preload_classes(&self, class_names: impl IntoIterator<Item = impl Into<String>>) {
-        let mut cache = CLASS_CACHE.lock();
+        let class_loader = self
+            .with_class_cache(|cache| cache.class_loader.clone())
+            .unwrap_or_else(|error| panic!("{}", error));
 
         for class_name in class_names {
             let class_name = class_name.into();
-            let class = self.load_class(&class_name);
+            let class = self
+                .load_class(&class_name, class_loader.as_ref())
+                .unwrap_or_else(|error| panic!("{}", error));
 
-            cache.insert(class_name, class);
+            self.with_class_cache(|cache| cache.classes.insert(class_name, class))
+                .unwrap_or_else(|error| panic!("{}", error));
         }
     }
 
-    fn load_class(&self, class_name: impl AsRef<str>) -> GlobalRef {
+    /// Registers a `ClassLoader` to use for classes that `FindClass` can't resolve.
+    ///
+    /// Threads attached from native code only see the system class loader through `FindClass`, so
+    /// application classes can only be found from those threads through the registered class
+    /// loader. It is usually captured in `JNI_OnLoad` or in the first call into native code.
+    ///
+    /// The class loader is registered for the current `JavaVM`, and is used by environments that
+    /// don't have their own class loader. Classes cached for other class loaders are kept.
+    pub fn set_class_loader(&self, class_loader: JObject<'env>) -> Result<(), Error> {
+        let class_loader = self
+            .env
+            .new_global_ref(class_loader)
+            .map_err(|error| self.convert_jni_error(error))?;
+
+        self.with_java_vm_caches(|caches| caches.class_loader = Some(class_loader))
+    }
+
+    fn load_class(
+        &self,
+        class_name: impl AsRef<str>,
+        class_loader: Option<&GlobalRef>,
+    ) -> Result<GlobalRef, Error> {
         let class_name = class_name.as_ref();
         log::debug!("JnixEnv::load_class({})", class_name);
-        let local_ref = self
+
+        // `FindClass` would resolve the name with a different class loader.
+        let class = if self.class_loader.is_some() {
+            self.load_class_with_class_loader(class_name, class_loader)?
+        } else {
+            match self.env.find_class(class_name) {
+                Ok(local_ref) => JObject::from(local_ref),
+                Err(_) => {
+                    self.take_pending_exception();
+                    self.load_class_with_class_loader(class_name, class_loader)?
+                }
+            }
+        };
+
+        self.env
+            .new_global_ref(class)
+            .map_err(|error| self.convert_jni_error(error))
+    }
+
+    fn load_class_with_class_loader(
+        &self,
+        class_name: &str,
+        class_loader: Option<&GlobalRef>,
+    ) -> Result<JObject<'env>, Error> {
+        let class_not_found = || Error::ClassNotFound(class_name.to_owned());
+        let class_loader = class_loader.ok_or_else(class_not_found)?;
+        log::debug!("JnixEnv::load_class_with_class_loader({})", class_name);
+
+        let binary_name = self
+            .env
+            .new_string(class_name.replace("/", "."))
+            .map_err(|error| self.convert_jni_error(error))?;
+        let binary_name = self.env.auto_local(binary_name.into());
+
+        let class = self
+            .env
+            .call_method(
+                JObject::from(class_loader.as_obj().into_inner()),
+                "loadClass",
+                "(Ljava/lang/String;)Ljava/lang/Class;",
+                &[JValue::Object(binary_name.as_obj())],
+            )
+            .and_then(|class| class.l())
+            .map_err(|_| {
+                self.take_pending_exception();
+
+                class_not_found()
+            })?;
+
+        if class.is_null() {
+            Err(class_not_found())
+        } else {
+            Ok(class)
+        }
+    }
+
+    /// Calls a static method that returns an object, like a factory method.
+    pub(crate) fn call_static_object_method<'borrow>(
+        &'borrow self,
+        class_name: &'static str,
+        method_name: &'static str,
+        signature: &'static str,
+        parameters: &[JValue],
+    ) -> Result<AutoLocal<'env, 'borrow>, Error> {
+        let class = self.try_get_class(class_name)?;
+        let method = self.get_cached_static_method_id(class_name, method_name, signature)?;
+
+        let result = self
+            .env
+            .call_static_method_unchecked(
+                &class,
+                method,
+                JavaType::Object(String::new()),
+                parameters,
+            )
+            .map_err(|error| self.convert_jni_error(error))?;
+
+        match result {
+            JValue::Object(object) => Ok(self.env.auto_local(object)),
+            value => Err(Error::InvalidReturnValue {
+                source: format!("{}.{}", class_name.replace("/", "."), method_name),
+                value_type: value.type_name(),
+            }),
+        }
+    }
+
+    /// Creates a Java exception object from a Rust error, without throwing it.
+    ///
+    /// Errors that hold a caught Java exception return the original exception object. Otherwise a
+    /// new exception is created with the message if its class has a constructor that receives a
+    /// `String`, or without the message if it only has a constructor without parameters.
+    pub fn new_exception<'borrow>(
+        &'borrow self,
+        error: impl IntoJavaException,
+    ) -> Result<AutoLocal<'env, 'borrow>, Error> {
+        if let Some(throwable) = error.exception_object() {
+            let exception = self
+                .env
+                .new_local_ref::<JObject>(JObject::from(throwable.as_obj().into_inner()))
+                .map_err(|error| self.convert_jni_error(error))?;
+
+            return Ok(self.env.auto_local(exception));
+        }
+
+        let class_name = error.exception_class_name();
+        let class = self.try_get_class(class_name.as_ref())?;
+
+        let exception =
+            match self.get_cached_method_id(class_name.as_ref(), "<init>", "(Ljava/lang/String;)V")
+            {
+                Ok(constructor) => {
+                    let message = error.exception_message().try_into_java(self)?;
+
+                    self.env.new_object_unchecked(
+                        &class,
+                        constructor,
+                        &[JValue::Object(message.as_obj())],
+                    )
+                }
+                Err(_) => {
+                    let constructor =
+                        self.get_cached_method_id(class_name.as_ref(), "<init>", "()V")?;
+
+                    self.env.new_object_unchecked(&class, constructor, &[])
+                }
+            }
+            .map_err(|error| self.convert_jni_error(error))?;
+
+        Ok(self.env.auto_local(exception))
+    }
+
+    /// Throws a Java exception created from a Rust error.
+    ///
+    /// The exception object is created by [`new_exception`](#method.new_exception), so errors that
+    /// hold a caught Java exception throw the original exception object again. The exception is
+    /// only raised in the JVM when control returns to it from the native method.
+    pub fn throw(&self, error: impl IntoJavaException) -> Result<(), Error> {
+        let exception = self.new_exception(error)?;
+
+        self.env
+            .throw(JThrowable::from(exception.as_obj().into_inner()))
+            .map_err(|error| self.convert_jni_error(error))
+    }
+
+    /// Converts the result of a native method into the value it should return to the JVM.
+    ///
+    /// The `Ok` value is converted into its Java representation. If the result is an `Err` or if
+    /// the conversion fails, the error is thrown as a Java exception and a null value is returned.
+    pub fn result_into_java<'borrow, T, E>(&'borrow self, result: Result<T, E>) -> T::JavaType
+    where
+        T: TryIntoJava<'borrow, 'env>,
+        T::JavaType: NullValue<'borrow, 'env>,
+        E: IntoJavaException,
+    {
+        let throw_result = match result {
+            Ok(value) => match value.try_into_java(self) {
+                Ok(java_value) => return java_value,
+                Err(error) => self.throw(error),
+            },
+            Err(error) => self.throw(error),
+        };
+
+        if let Err(error) = throw_result {
+            log::error!("Failed to throw Java exception: {}", error);
+        }
+
+        T::JavaType::null_value(self)
+    }
+
+    /// Captures and clears the pending Java exception, if there is one.
+    pub fn take_pending_exception(&self) -> Option<JavaException> {
+        if !self.env.exception_check().unwrap_or(false) {
+            return None;
+        }
+
+        let throwable = self
             .env
-            .find_class(class_name)
-            .expect(&format!("Failed to find {} Java class", class_name));
+            .auto_local(self.env.exception_occurred().ok()?.into());
+
+        self.env.exception_clear().ok()?;
+
+        Some(JavaException::from_throwable(self, throwable.as_obj()))
+    }
+
+    /// Converts an error returned by a JNI call, capturing and clearing the pending Java exception
+    /// if the call failed because one was thrown.
+    pub fn convert_jni_error(&self, error: JniError) -> Error {
+        if let JniErrorKind::JavaException = error.kind() {
+            if let Some(exception) = self.take_pending_exception() {
+                return Error::JavaException(exception);
+            }
+        }
 
-        self.env.new_global_ref(JObject::from(local_ref)).expect(
-            "Failed to convert local reference to Java class object into a global reference",
-        )
+        Error::Jni(error)
     }
 }
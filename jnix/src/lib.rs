@@ -1,9 +1,37 @@
 pub extern crate jni;
 
+mod array;
 mod as_jvalue;
+mod boxed;
+mod class_cache;
+pub mod collections;
+pub mod const_signature;
+mod error;
+mod from_java;
+mod from_jvalue;
 mod into_java;
+mod into_java_exception;
+mod java_exception;
+mod java_string;
 mod jnix_env;
+pub mod os_str;
+pub mod result;
+pub mod time;
+pub mod tuples;
+pub mod unsigned;
 
-pub use self::{as_jvalue::AsJValue, into_java::IntoJava, jnix_env::JnixEnv};
+pub use self::{
+    array::Array,
+    as_jvalue::AsJValue,
+    boxed::{Boxable, Boxed},
+    class_cache::on_unload,
+    error::Error,
+    from_java::FromJava,
+    from_jvalue::FromJValue,
+    into_java::{ArrayElement, IntoJava, SliceElement, TryIntoJava, TypedArrayElement},
+    into_java_exception::{IntoJavaException, NullValue},
+    java_exception::{JavaException, StackTraceElement},
+    jnix_env::JnixEnv,
+};
 #[cfg(feature = "derive")]
-pub use jnix_macros::IntoJava;
+pub use jnix_macros::{FromJava, IntoJava, IntoJavaException};
@@ -0,0 +1,164 @@
+//! Conversions of OS strings and paths, which may not be valid Unicode.
+//!
+//! `OsStr` and `OsString` values have no default conversion, because there is no conversion that
+//! is correct for every use. Instead, they are converted with an explicit [`OsStrPolicy`]. Paths
+//! are converted into `java.io.File` by default, failing if they aren't valid Unicode. The wrappers
+//! in this module are used by the derive macros when a field has the `#[jnix(os_str = "...")]` or
+//! `#[jnix(path = "...")]` attributes.
+//!
+//! [`OsStrPolicy`]: enum.OsStrPolicy.html
+
+use crate::{Error, JnixEnv, TryIntoJava};
+use jni::{
+    objects::{AutoLocal, JObject, JValue},
+    signature::JavaType,
+};
+use std::{borrow::Cow, ffi::OsStr, path::Path};
+
+/// How to convert an OS string that isn't valid Unicode.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OsStrPolicy {
+    /// Fail the conversion with an [`Error::UnrepresentableValue`].
+    ///
+    /// [`Error::UnrepresentableValue`]: ../enum.Error.html#variant.UnrepresentableValue
+    Strict,
+
+    /// Replace invalid sequences with `U+FFFD REPLACEMENT CHARACTER`.
+    Lossy,
+}
+
+impl OsStrPolicy {
+    /// Converts an OS string into a Rust string according to the policy.
+    pub fn convert(self, value: &OsStr) -> Result<Cow<'_, str>, Error> {
+        match self {
+            OsStrPolicy::Strict => {
+                value
+                    .to_str()
+                    .map(Cow::Borrowed)
+                    .ok_or_else(|| Error::UnrepresentableValue {
+                        value: format!("{:?}", value),
+                        java_type: "String",
+                    })
+            }
+            OsStrPolicy::Lossy => Ok(value.to_string_lossy()),
+        }
+    }
+}
+
+/// Converts an OS string into a `java.lang.String`.
+pub struct JavaString<T> {
+    value: T,
+    policy: OsStrPolicy,
+}
+
+impl<T> JavaString<T> {
+    pub fn new(value: T, policy: OsStrPolicy) -> Self {
+        JavaString { value, policy }
+    }
+}
+
+impl<'borrow, 'env, T> TryIntoJava<'borrow, 'env> for JavaString<T>
+where
+    'env: 'borrow,
+    T: AsRef<OsStr>,
+{
+    const JNI_SIGNATURE: &'static str = "Ljava/lang/String;";
+
+    type JavaType = AutoLocal<'env, 'borrow>;
+
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        self.policy.convert(self.value.as_ref())?.try_into_java(env)
+    }
+}
+
+/// Converts a path into a `java.io.File`.
+pub struct JavaFile<T> {
+    path: T,
+    policy: OsStrPolicy,
+}
+
+impl<T> JavaFile<T> {
+    pub fn new(path: T, policy: OsStrPolicy) -> Self {
+        JavaFile { path, policy }
+    }
+}
+
+impl<'borrow, 'env, T> TryIntoJava<'borrow, 'env> for JavaFile<T>
+where
+    'env: 'borrow,
+    T: AsRef<Path>,
+{
+    const JNI_SIGNATURE: &'static str = "Ljava/io/File;";
+
+    type JavaType = AutoLocal<'env, 'borrow>;
+
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        let path = JavaString::new(self.path.as_ref(), self.policy).try_into_java(env)?;
+        let parameters = [JValue::Object(path.as_obj())];
+
+        let class = env.try_get_class("java/io/File")?;
+        let constructor =
+            env.get_cached_method_id("java/io/File", "<init>", "(Ljava/lang/String;)V")?;
+        let object = env
+            .new_object_unchecked(&class, constructor, &parameters)
+            .map_err(|error| env.convert_jni_error(error))?;
+
+        Ok(env.auto_local(object))
+    }
+}
+
+/// Converts a path into a `java.nio.file.Path`, using `java.nio.file.Paths.get`.
+pub struct JavaPath<T> {
+    path: T,
+    policy: OsStrPolicy,
+}
+
+impl<T> JavaPath<T> {
+    pub fn new(path: T, policy: OsStrPolicy) -> Self {
+        JavaPath { path, policy }
+    }
+}
+
+impl<'borrow, 'env, T> TryIntoJava<'borrow, 'env> for JavaPath<T>
+where
+    'env: 'borrow,
+    T: AsRef<Path>,
+{
+    const JNI_SIGNATURE: &'static str = "Ljava/nio/file/Path;";
+
+    type JavaType = AutoLocal<'env, 'borrow>;
+
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        let path = JavaString::new(self.path.as_ref(), self.policy).try_into_java(env)?;
+
+        let string_class = env.try_get_class("java/lang/String")?;
+        let more = env
+            .new_object_array(0, &string_class, JObject::null())
+            .map_err(|error| env.convert_jni_error(error))?;
+        let more = env.auto_local(JObject::from(more));
+
+        let class = env.try_get_class("java/nio/file/Paths")?;
+        let get_method = env.get_cached_static_method_id(
+            "java/nio/file/Paths",
+            "get",
+            "(Ljava/lang/String;[Ljava/lang/String;)Ljava/nio/file/Path;",
+        )?;
+
+        let result = env
+            .call_static_method_unchecked(
+                &class,
+                get_method,
+                JavaType::Object("java/nio/file/Path".to_owned()),
+                &[JValue::Object(path.as_obj()), JValue::Object(more.as_obj())],
+            )
+            .map_err(|error| env.convert_jni_error(error))?;
+
+        match result {
+            JValue::Object(object) => Ok(env.auto_local(object)),
+            value => Err(Error::InvalidReturnValue {
+                source: "Paths.get".to_owned(),
+                value_type: value.type_name(),
+            }),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Conversions for `Result` values.
+//!
+//! By default, a `Result` is converted into its `Ok` value, and its `Err` value fails the conversion
+//! with an [`Error::Exception`], which is thrown as a Java exception when the conversion reaches
+//! [`JnixEnv::result_into_java`]. The [`KotlinResult`] wrapper converts it into a `kotlin.Result`
+//! instead, and is used by the derive macros when a field has the `#[jnix(result = "kotlin")]`
+//! attribute. Fields with the `#[jnix(result_class = "...")]` attribute are converted into a sealed
+//! class with `Ok` and `Err` subclasses.
+//!
+//! [`Error::Exception`]: ../enum.Error.html#variant.Exception
+//! [`JnixEnv::result_into_java`]: ../struct.JnixEnv.html#method.result_into_java
+//! [`KotlinResult`]: struct.KotlinResult.html
+
+use crate::{Boxable, Boxed, Error, IntoJavaException, JnixEnv, TryIntoJava};
+use jni::objects::{AutoLocal, JValue};
+
+/// Converts a `Result` into a boxed `kotlin.Result`.
+///
+/// The `Err` value is converted into the failure exception through its [`IntoJavaException`]
+/// implementation. The boxed representation is the one Kotlin uses when a `Result` is stored as a
+/// nullable or generic value, like a `Result<Int>?` property, so the `Lkotlin/Result;` signature
+/// only matches those. Kotlin compiles a non-null `Result<T>` property or constructor parameter
+/// into its unboxed value with the `Ljava/lang/Object;` signature, which this wrapper can't be
+/// passed to.
+///
+/// [`IntoJavaException`]: ../trait.IntoJavaException.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct KotlinResult<R>(pub R);
+
+impl<'borrow, 'env, T, E> TryIntoJava<'borrow, 'env> for KotlinResult<Result<T, E>>
+where
+    'env: 'borrow,
+    T: TryIntoJava<'borrow, 'env>,
+    T::JavaType: Boxable<'borrow, 'env>,
+    E: IntoJavaException,
+{
+    const JNI_SIGNATURE: &'static str = "Lkotlin/Result;";
+
+    type JavaType = AutoLocal<'env, 'borrow>;
+
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        let value = match self.0 {
+            Ok(value) => Boxed(value).try_into_java(env)?,
+            Err(error) => {
+                let exception = env.new_exception(error)?;
+
+                env.call_static_object_method(
+                    "kotlin/ResultKt",
+                    "createFailure",
+                    "(Ljava/lang/Throwable;)Ljava/lang/Object;",
+                    &[JValue::Object(exception.as_obj())],
+                )?
+            }
+        };
+
+        env.call_static_object_method(
+            "kotlin/Result",
+            "box-impl",
+            "(Ljava/lang/Object;)Lkotlin/Result;",
+            &[JValue::Object(value.as_obj())],
+        )
+    }
+}
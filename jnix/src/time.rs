@@ -0,0 +1,177 @@
+//! Conversions for time values.
+//!
+//! Durations are converted into `java.time.Duration` and points in time into `java.time.Instant`.
+//! With the `chrono` or `time` features enabled, the date and time types of those crates are also
+//! converted into `java.time` types. The [`Millis`] wrapper converts them into a `long` number of
+//! milliseconds instead, and is used by the derive macros when a field has the `#[jnix(millis)]`
+//! attribute.
+//!
+//! [`Millis`]: struct.Millis.html
+
+use crate::{Error, JnixEnv, TryIntoJava};
+use jni::{
+    objects::{AutoLocal, JValue},
+    sys::jlong,
+};
+use std::{
+    convert::TryFrom,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+use jni::sys::jint;
+
+/// Converts a time value into a Java `long` number of milliseconds.
+///
+/// Durations are converted into their length, and points in time into the number of milliseconds
+/// since the Unix epoch. Precision finer than a millisecond is truncated towards negative infinity,
+/// like `java.time.Instant.toEpochMilli` does.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Millis<T>(pub T);
+
+impl<'borrow, 'env: 'borrow> TryIntoJava<'borrow, 'env> for Millis<Duration> {
+    const JNI_SIGNATURE: &'static str = "J";
+
+    type JavaType = jlong;
+
+    fn try_into_java(self, _: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        millis_to_jlong(self.0.as_millis() as i128, &self.0)
+    }
+}
+
+impl<'borrow, 'env: 'borrow> TryIntoJava<'borrow, 'env> for Millis<SystemTime> {
+    const JNI_SIGNATURE: &'static str = "J";
+
+    type JavaType = jlong;
+
+    fn try_into_java(self, _: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        let millis = match self.0.duration_since(UNIX_EPOCH) {
+            Ok(since_epoch) => since_epoch.as_millis() as i128,
+            Err(error) => {
+                let before_epoch = error.duration();
+                let partial_milli = before_epoch.subsec_nanos() % 1_000_000 != 0;
+
+                -(before_epoch.as_millis() as i128) - i128::from(partial_milli)
+            }
+        };
+
+        millis_to_jlong(millis, &self.0)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<'borrow, 'env, Tz> TryIntoJava<'borrow, 'env> for Millis<::chrono::DateTime<Tz>>
+where
+    'env: 'borrow,
+    Tz: ::chrono::TimeZone,
+{
+    const JNI_SIGNATURE: &'static str = "J";
+
+    type JavaType = jlong;
+
+    fn try_into_java(self, _: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        Ok(self.0.timestamp_millis())
+    }
+}
+
+#[cfg(feature = "time")]
+impl<'borrow, 'env: 'borrow> TryIntoJava<'borrow, 'env> for Millis<::time::OffsetDateTime> {
+    const JNI_SIGNATURE: &'static str = "J";
+
+    type JavaType = jlong;
+
+    fn try_into_java(self, _: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        let millis = self.0.unix_timestamp_nanos().div_euclid(1_000_000);
+
+        millis_to_jlong(millis, &self.0)
+    }
+}
+
+#[cfg(feature = "time")]
+impl<'borrow, 'env: 'borrow> TryIntoJava<'borrow, 'env> for Millis<::time::Duration> {
+    const JNI_SIGNATURE: &'static str = "J";
+
+    type JavaType = jlong;
+
+    fn try_into_java(self, _: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        millis_to_jlong(self.0.whole_milliseconds(), &self.0)
+    }
+}
+
+fn millis_to_jlong(millis: i128, value: &impl std::fmt::Debug) -> Result<jlong, Error> {
+    jlong::try_from(millis).map_err(|_| Error::UnrepresentableValue {
+        value: format!("{:?}", value),
+        java_type: "long number of milliseconds",
+    })
+}
+
+/// Creates a `java.time.Duration` from a number of seconds and a nanosecond adjustment.
+pub(crate) fn new_duration<'borrow, 'env: 'borrow>(
+    seconds: jlong,
+    nanos: jlong,
+    env: &'borrow JnixEnv<'env>,
+) -> Result<AutoLocal<'env, 'borrow>, Error> {
+    env.call_static_object_method(
+        "java/time/Duration",
+        "ofSeconds",
+        "(JJ)Ljava/time/Duration;",
+        &[JValue::Long(seconds), JValue::Long(nanos)],
+    )
+}
+
+/// Creates a `java.time.Instant` from the seconds since the Unix epoch and a nanosecond adjustment.
+pub(crate) fn new_instant<'borrow, 'env: 'borrow>(
+    seconds: jlong,
+    nanos: jlong,
+    env: &'borrow JnixEnv<'env>,
+) -> Result<AutoLocal<'env, 'borrow>, Error> {
+    env.call_static_object_method(
+        "java/time/Instant",
+        "ofEpochSecond",
+        "(JJ)Ljava/time/Instant;",
+        &[JValue::Long(seconds), JValue::Long(nanos)],
+    )
+}
+
+/// Creates a `java.time.ZonedDateTime` at an instant, in a zone with a fixed UTC offset.
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub(crate) fn new_zoned_date_time<'borrow, 'env: 'borrow>(
+    seconds: jlong,
+    nanos: jlong,
+    offset_seconds: jint,
+    env: &'borrow JnixEnv<'env>,
+) -> Result<AutoLocal<'env, 'borrow>, Error> {
+    let instant = new_instant(seconds, nanos, env)?;
+    let zone = env.call_static_object_method(
+        "java/time/ZoneOffset",
+        "ofTotalSeconds",
+        "(I)Ljava/time/ZoneOffset;",
+        &[JValue::Int(offset_seconds)],
+    )?;
+
+    env.call_static_object_method(
+        "java/time/ZonedDateTime",
+        "ofInstant",
+        "(Ljava/time/Instant;Ljava/time/ZoneId;)Ljava/time/ZonedDateTime;",
+        &[
+            JValue::Object(instant.as_obj()),
+            JValue::Object(zone.as_obj()),
+        ],
+    )
+}
+
+/// Creates a `java.time.LocalDate` from a year, a month from 1 to 12 and a day of the month.
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub(crate) fn new_local_date<'borrow, 'env: 'borrow>(
+    year: jint,
+    month: jint,
+    day: jint,
+    env: &'borrow JnixEnv<'env>,
+) -> Result<AutoLocal<'env, 'borrow>, Error> {
+    env.call_static_object_method(
+        "java/time/LocalDate",
+        "of",
+        "(III)Ljava/time/LocalDate;",
+        &[JValue::Int(year), JValue::Int(month), JValue::Int(day)],
+    )
+}
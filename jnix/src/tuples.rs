@@ -0,0 +1,115 @@
+//! Conversions of Rust tuples into Java objects.
+//!
+//! Pairs and triples are converted into `kotlin.Pair` and `kotlin.Triple` by default, and larger
+//! tuples are converted into `Object[]`. The elements are always boxed. A [`TupleClass`] converts
+//! a tuple into an instance of another class, like `android.util.Pair`. It is used by the derive
+//! macros when a field has the `#[jnix(tuple_class = "...")]` attribute.
+//!
+//! [`TupleClass`]: struct.TupleClass.html
+
+use crate::{Boxable, Boxed, Error, JnixEnv, TryIntoJava};
+use jni::objects::{AutoLocal, JValue};
+
+/// Tuples whose elements can be converted into boxed Java objects.
+pub trait TupleElements<'borrow, 'env: 'borrow> {
+    /// The signature of a constructor that receives each element as a `java.lang.Object`.
+    const CONSTRUCTOR_SIGNATURE: &'static str;
+
+    fn into_java_elements(
+        self,
+        env: &'borrow JnixEnv<'env>,
+    ) -> Result<Vec<AutoLocal<'env, 'borrow>>, Error>;
+}
+
+macro_rules! object_signature {
+    ($element_type:ident) => {
+        "Ljava/lang/Object;"
+    };
+}
+
+macro_rules! impl_tuple_elements {
+    ( $( ( $( $element_type:ident $index:tt ),* ) )* ) => {
+        $(
+            impl<'borrow, 'env, $( $element_type ),*> TupleElements<'borrow, 'env>
+                for ( $( $element_type, )* )
+            where
+                'env: 'borrow,
+                $(
+                    $element_type: TryIntoJava<'borrow, 'env>,
+                    $element_type::JavaType: Boxable<'borrow, 'env>,
+                )*
+            {
+                const CONSTRUCTOR_SIGNATURE: &'static str =
+                    concat!("(", $( object_signature!($element_type), )* ")V");
+
+                fn into_java_elements(
+                    self,
+                    env: &'borrow JnixEnv<'env>,
+                ) -> Result<Vec<AutoLocal<'env, 'borrow>>, Error> {
+                    Ok(vec![ $( Boxed(self.$index).try_into_java(env)? ),* ])
+                }
+            }
+        )*
+    };
+}
+
+impl_tuple_elements! {
+    (A 0, B 1)
+    (A 0, B 1, C 2)
+    (A 0, B 1, C 2, D 3)
+    (A 0, B 1, C 2, D 3, E 4)
+    (A 0, B 1, C 2, D 3, E 4, F 5)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11)
+}
+
+/// Converts a tuple into an instance of the given Java class.
+///
+/// The class must have a public constructor that receives each element as a `java.lang.Object`.
+/// Since the class is only known at runtime, the JNI signature is `Ljava/lang/Object;`.
+pub struct TupleClass<T> {
+    class_name: &'static str,
+    elements: T,
+}
+
+impl<T> TupleClass<T> {
+    /// Creates the wrapper for a tuple, with the Java class name in JNI format (e.g.
+    /// `android/util/Pair`).
+    pub fn new(class_name: &'static str, elements: T) -> Self {
+        TupleClass {
+            class_name,
+            elements,
+        }
+    }
+}
+
+impl<'borrow, 'env, T> TryIntoJava<'borrow, 'env> for TupleClass<T>
+where
+    'env: 'borrow,
+    T: TupleElements<'borrow, 'env>,
+{
+    const JNI_SIGNATURE: &'static str = "Ljava/lang/Object;";
+
+    type JavaType = AutoLocal<'env, 'borrow>;
+
+    fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+        let elements = self.elements.into_java_elements(env)?;
+        let parameters: Vec<_> = elements
+            .iter()
+            .map(|element| JValue::Object(element.as_obj()))
+            .collect();
+
+        let class = env.try_get_class(self.class_name)?;
+        let constructor =
+            env.get_cached_method_id(self.class_name, "<init>", T::CONSTRUCTOR_SIGNATURE)?;
+        let object = env
+            .new_object_unchecked(&class, constructor, &parameters)
+            .map_err(|error| env.convert_jni_error(error))?;
+
+        Ok(env.auto_local(object))
+    }
+}
@@ -0,0 +1,101 @@
+//! Alternative conversions for unsigned integers.
+//!
+//! By default, unsigned integers are converted into the signed Java type with the same width,
+//! reinterpreting their bits. The wrappers in this module convert them into a wider Java type
+//! instead, so that the value is preserved. They are used by the derive macros when a field has the
+//! `#[jnix(unsigned = "widen")]` or `#[jnix(unsigned = "big_integer")]` attribute.
+
+use crate::{Error, JnixEnv, TryIntoJava};
+use jni::{
+    objects::{AutoLocal, JValue},
+    sys::{jint, jlong, jshort},
+};
+
+/// Converts an unsigned integer into the next wider signed Java primitive type.
+///
+/// `u8` becomes `short`, `u16` becomes `int` and `u32` becomes `long`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Widened<T>(pub T);
+
+macro_rules! impl_into_java_for_widened {
+    ( $( $unsigned:ty => $java_type:ty, $signature:expr ),* $(,)* ) => {
+        $(
+            impl<'borrow, 'env: 'borrow> TryIntoJava<'borrow, 'env> for Widened<$unsigned> {
+                const JNI_SIGNATURE: &'static str = $signature;
+
+                type JavaType = $java_type;
+
+                fn try_into_java(self, _: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+                    Ok(<$java_type>::from(self.0))
+                }
+            }
+        )*
+    };
+}
+
+impl_into_java_for_widened! {
+    u8 => jshort, "S",
+    u16 => jint, "I",
+    u32 => jlong, "J",
+}
+
+/// Converts an integer into a `java.math.BigInteger`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BigInteger<T>(pub T);
+
+macro_rules! impl_into_java_for_big_integer {
+    ( $( $integer:ty, $is_unsigned:expr );* $(;)* ) => {
+        $(
+            impl<'borrow, 'env: 'borrow> TryIntoJava<'borrow, 'env> for BigInteger<$integer> {
+                const JNI_SIGNATURE: &'static str = "Ljava/math/BigInteger;";
+
+                type JavaType = AutoLocal<'env, 'borrow>;
+
+                fn try_into_java(self, env: &'borrow JnixEnv<'env>) -> Result<Self::JavaType, Error> {
+                    let mut bytes = Vec::with_capacity(std::mem::size_of::<$integer>() + 1);
+
+                    // The bytes are interpreted as a two's complement number, so a leading zero
+                    // byte is needed to keep unsigned values positive.
+                    if $is_unsigned {
+                        bytes.push(0);
+                    }
+
+                    bytes.extend_from_slice(&self.0.to_be_bytes());
+
+                    big_integer_from_bytes(&bytes, env)
+                }
+            }
+        )*
+    };
+}
+
+impl_into_java_for_big_integer! {
+    i8, false;
+    u8, true;
+    i16, false;
+    u16, true;
+    i32, false;
+    u32, true;
+    i64, false;
+    u64, true;
+    i128, false;
+    u128, true;
+    isize, false;
+    usize, true;
+}
+
+fn big_integer_from_bytes<'borrow, 'env: 'borrow>(
+    bytes: &[u8],
+    env: &'borrow JnixEnv<'env>,
+) -> Result<AutoLocal<'env, 'borrow>, Error> {
+    let java_bytes = bytes.try_into_java(env)?;
+    let parameters = [JValue::Object(java_bytes.as_obj())];
+
+    let class = env.try_get_class("java/math/BigInteger")?;
+    let constructor = env.get_cached_method_id("java/math/BigInteger", "<init>", "([B)V")?;
+    let object = env
+        .new_object_unchecked(&class, constructor, &parameters)
+        .map_err(|error| env.convert_jni_error(error))?;
+
+    Ok(env.auto_local(object))
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use common::with_env;
+use jnix::{IntoJava, TryIntoJava};
+use jni::sys::{jboolean, jbyteArray, jcharArray, jintArray, jlongArray, JNI_FALSE, JNI_TRUE};
+
+#[test]
+fn slices_are_converted_into_primitive_arrays() {
+    with_env(|env| {
+        let bytes = [0_u8, 1, 0x7f, 0x80, 0xff];
+        let java_bytes = bytes.as_ref().into_java(env);
+        let mut converted_bytes = [0; 5];
+
+        env.get_byte_array_region(
+            java_bytes.as_obj().into_inner() as jbyteArray,
+            0,
+            &mut converted_bytes,
+        )
+        .unwrap();
+
+        assert_eq!(<&[u8] as IntoJava>::JNI_SIGNATURE, "[B");
+        assert_eq!(converted_bytes, [0, 1, 0x7f, -0x80, -1]);
+
+        let integers = [i32::MIN, -1, 0, i32::MAX];
+        let java_integers = integers.as_ref().into_java(env);
+        let mut converted_integers = [0; 4];
+
+        env.get_int_array_region(
+            java_integers.as_obj().into_inner() as jintArray,
+            0,
+            &mut converted_integers,
+        )
+        .unwrap();
+
+        assert_eq!(<&[i32] as IntoJava>::JNI_SIGNATURE, "[I");
+        assert_eq!(converted_integers, integers);
+
+        let sizes = [0_usize, 1, usize::MAX];
+        let java_sizes = sizes.as_ref().into_java(env);
+        let mut converted_sizes = [0; 3];
+
+        env.get_long_array_region(
+            java_sizes.as_obj().into_inner() as jlongArray,
+            0,
+            &mut converted_sizes,
+        )
+        .unwrap();
+
+        assert_eq!(<&[usize] as IntoJava>::JNI_SIGNATURE, "[J");
+        assert_eq!(converted_sizes, [0, 1, -1]);
+
+        let flags = [true, false];
+        let java_flags = flags.as_ref().into_java(env);
+        let mut converted_flags: [jboolean; 2] = [0; 2];
+
+        env.get_boolean_array_region(java_flags.as_obj().into_inner() as _, 0, &mut converted_flags)
+            .unwrap();
+
+        assert_eq!(<&[bool] as IntoJava>::JNI_SIGNATURE, "[Z");
+        assert_eq!(converted_flags, [JNI_TRUE, JNI_FALSE]);
+    });
+}
+
+#[test]
+fn unrepresentable_slice_elements_are_rejected() {
+    with_env(|env| {
+        let characters = ['a', '\u{1f980}'];
+
+        assert!(characters.as_ref().try_into_java(env).is_err());
+
+        let characters = ['a', 'é'];
+        let java_characters = characters.as_ref().into_java(env);
+        let mut converted_characters = [0; 2];
+
+        env.get_char_array_region(
+            java_characters.as_obj().into_inner() as jcharArray,
+            0,
+            &mut converted_characters,
+        )
+        .unwrap();
+
+        assert_eq!(converted_characters, [0x61, 0xe9]);
+    });
+}
@@ -0,0 +1,137 @@
+mod common;
+
+use common::with_env;
+use jni::objects::{JObject, JValue};
+use jnix::JnixEnv;
+use std::{
+    env, fs,
+    path::PathBuf,
+    process::Command,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+static NEXT_OUTPUT_DIRECTORY: AtomicUsize = AtomicUsize::new(0);
+
+/// A directory with compiled test classes, which is removed when dropped.
+struct OutputDirectory(PathBuf);
+
+impl Drop for OutputDirectory {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Compiles one of the `Probe` test classes and creates a class loader that loads it.
+fn probe_class_loader<'env>(
+    env: &JnixEnv<'env>,
+    variant: &str,
+) -> (JObject<'env>, OutputDirectory) {
+    let source = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/classes")
+        .join(variant)
+        .join("Probe.java");
+    let output_directory = env::temp_dir().join(format!(
+        "jnix-tests-{}-{}",
+        std::process::id(),
+        NEXT_OUTPUT_DIRECTORY.fetch_add(1, Ordering::Relaxed),
+    ));
+    let javac = env::var_os("JAVA_HOME")
+        .map(|java_home| PathBuf::from(java_home).join("bin/javac"))
+        .unwrap_or_else(|| PathBuf::from("javac"));
+
+    let status = Command::new(javac)
+        .arg("-d")
+        .arg(&output_directory)
+        .arg(&source)
+        .status()
+        .expect("Failed to run javac");
+
+    assert!(status.success(), "Failed to compile {}", source.display());
+
+    let path = env
+        .new_string(output_directory.to_str().unwrap())
+        .unwrap();
+    let file = env
+        .new_object(
+            "java/io/File",
+            "(Ljava/lang/String;)V",
+            &[JValue::Object(path.into())],
+        )
+        .unwrap();
+    let uri = env
+        .call_method(file, "toURI", "()Ljava/net/URI;", &[])
+        .and_then(|uri| uri.l())
+        .unwrap();
+    let url = env
+        .call_method(uri, "toURL", "()Ljava/net/URL;", &[])
+        .and_then(|url| url.l())
+        .unwrap();
+    let urls = env
+        .new_object_array(1, "java/net/URL", url)
+        .unwrap();
+
+    let class_loader = env
+        .new_object(
+            "java/net/URLClassLoader",
+            "([Ljava/net/URL;Ljava/lang/ClassLoader;)V",
+            &[JValue::Object(urls.into()), JValue::Object(JObject::null())],
+        )
+        .unwrap();
+
+    (class_loader, OutputDirectory(output_directory))
+}
+
+fn probe_value(env: &JnixEnv) -> i32 {
+    let class = env.try_get_class("Probe").unwrap();
+    let field_id = env
+        .get_cached_static_field_id("Probe", "VALUE", "I")
+        .unwrap();
+
+    env.get_static_field_unchecked(
+        &class,
+        field_id,
+        jni::signature::JavaType::Primitive(jni::signature::Primitive::Int),
+    )
+    .and_then(|value| value.i())
+    .unwrap()
+}
+
+#[test]
+fn classes_are_cached_for_each_class_loader() {
+    with_env(|env| {
+        let (first_class_loader, _first_classes) = probe_class_loader(env, "first");
+        let (second_class_loader, _second_classes) = probe_class_loader(env, "second");
+
+        let first_env = JnixEnv::from((**env).clone())
+            .with_class_loader(first_class_loader)
+            .unwrap();
+        let second_env = JnixEnv::from((**env).clone())
+            .with_class_loader(second_class_loader)
+            .unwrap();
+
+        assert_eq!(probe_value(&first_env), 1);
+        assert_eq!(probe_value(&second_env), 2);
+        assert_eq!(probe_value(&first_env), 1);
+    });
+}
+
+#[test]
+fn registered_class_loader_keeps_other_caches() {
+    with_env(|env| {
+        let (first_class_loader, _first_classes) = probe_class_loader(env, "first");
+        let (second_class_loader, _second_classes) = probe_class_loader(env, "second");
+
+        let first_env = JnixEnv::from((**env).clone())
+            .with_class_loader(first_class_loader)
+            .unwrap();
+
+        assert_eq!(probe_value(&first_env), 1);
+
+        env.set_class_loader(second_class_loader).unwrap();
+
+        assert_eq!(probe_value(env), 2);
+        assert_eq!(probe_value(&first_env), 1);
+
+        env.clear_class_cache().unwrap();
+    });
+}
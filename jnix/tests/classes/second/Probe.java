@@ -0,0 +1,3 @@
+public class Probe {
+    public static final int VALUE = 2;
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use common::with_env;
+use jni::objects::{JObject, JValue};
+use jnix::{collections::Unmodifiable, IntoJava, JnixEnv};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+fn assert_unmodifiable<'env>(env: &JnixEnv<'env>, collection: JObject<'env>, expected_size: i32) {
+    let size = env
+        .call_method(collection, "size", "()I", &[])
+        .and_then(JValue::i)
+        .unwrap();
+
+    assert_eq!(size, expected_size);
+
+    let result = env.call_method(collection, "clear", "()V", &[]);
+
+    assert!(result.is_err());
+    assert!(env.exception_check().unwrap());
+
+    let exception = env.exception_occurred().unwrap();
+
+    env.exception_clear().unwrap();
+
+    assert!(env
+        .is_instance_of(exception.into(), "java/lang/UnsupportedOperationException")
+        .unwrap());
+}
+
+#[test]
+fn collections_are_converted_into_unmodifiable_views() {
+    with_env(|env| {
+        let list = Unmodifiable(vec![1, 2, 3]).into_java(env);
+        assert_unmodifiable(env, list.as_obj(), 3);
+
+        let collection = Unmodifiable(VecDeque::from(vec![1])).into_java(env);
+        assert_unmodifiable(env, collection.as_obj(), 1);
+
+        let set = Unmodifiable(BTreeSet::from([1, 2])).into_java(env);
+        assert_unmodifiable(env, set.as_obj(), 2);
+
+        let map = Unmodifiable(BTreeMap::from([(1, "one")])).into_java(env);
+        assert_unmodifiable(env, map.as_obj(), 1);
+
+        assert_eq!(
+            <Unmodifiable<Vec<i32>> as IntoJava>::JNI_SIGNATURE,
+            "Ljava/util/List;"
+        );
+    });
+}
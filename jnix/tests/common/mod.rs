@@ -0,0 +1,26 @@
+//! A JVM shared by the tests in each test binary.
+//!
+//! The tests link to the JVM library, so it must be in the library search path when they run, for
+//! example with `LD_LIBRARY_PATH=$JAVA_HOME/lib/server` on Linux.
+
+use jni::{InitArgsBuilder, JNIVersion, JavaVM};
+use jnix::JnixEnv;
+use once_cell::sync::Lazy;
+
+static JAVA_VM: Lazy<JavaVM> = Lazy::new(|| {
+    let arguments = InitArgsBuilder::new()
+        .version(JNIVersion::V8)
+        .build()
+        .expect("Invalid Java VM arguments");
+
+    JavaVM::new(arguments).expect("Failed to create Java VM")
+});
+
+/// Runs a test with an environment attached to the shared JVM.
+pub fn with_env(test: impl FnOnce(&JnixEnv)) {
+    let env = JAVA_VM
+        .attach_current_thread_permanently()
+        .expect("Failed to attach thread to Java VM");
+
+    test(&JnixEnv::from(env));
+}
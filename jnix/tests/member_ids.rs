@@ -0,0 +1,42 @@
+mod common;
+
+use common::with_env;
+use jnix::Error;
+
+#[test]
+fn missing_members_are_not_found() {
+    with_env(|env| {
+        for _ in 0..2 {
+            match env.get_cached_method_id("java/lang/Object", "missing", "()V") {
+                Err(Error::MethodNotFound { method_name, .. }) => assert_eq!(method_name, "missing"),
+                result => panic!("Unexpected method lookup result: {:?}", result.map(|_| ())),
+            }
+
+            match env.get_cached_static_field_id("java/lang/Integer", "MISSING", "I") {
+                Err(Error::FieldNotFound { field_name, .. }) => assert_eq!(field_name, "MISSING"),
+                result => panic!("Unexpected field lookup result: {:?}", result.map(|_| ())),
+            }
+        }
+
+        assert!(!env.exception_check().unwrap());
+    });
+}
+
+#[test]
+fn members_are_found_after_a_failed_lookup() {
+    with_env(|env| {
+        env.try_get_class("java/lang/String").unwrap();
+        env.throw_new("java/lang/IllegalStateException", "Pending exception")
+            .unwrap();
+
+        match env.get_cached_method_id("java/lang/String", "length", "()I") {
+            Err(Error::JavaException(exception)) => {
+                assert_eq!(exception.class_name, "java.lang.IllegalStateException")
+            }
+            result => panic!("Unexpected method lookup result: {:?}", result.map(|_| ())),
+        }
+
+        assert!(!env.exception_check().unwrap());
+        assert!(env.get_cached_method_id("java/lang/String", "length", "()I").is_ok());
+    });
+}
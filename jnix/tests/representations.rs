@@ -0,0 +1,116 @@
+#![cfg(feature = "derive")]
+
+mod common;
+
+use common::with_env;
+use jnix::{FromJava, IntoJava};
+
+#[derive(Clone, Copy, Debug, Eq, FromJava, IntoJava, PartialEq)]
+#[jnix(repr = "ordinal")]
+enum Ordinal {
+    First,
+    Second,
+    Third,
+}
+
+#[derive(Clone, Copy, Debug, Eq, FromJava, IntoJava, PartialEq)]
+#[jnix(repr = "discriminant")]
+#[repr(i64)]
+enum Discriminant {
+    Negative = -1,
+    Large = 0x7fff_ffff,
+}
+
+#[derive(Clone, Copy, Debug, Eq, FromJava, IntoJava, PartialEq)]
+#[jnix(repr = "name")]
+#[jnix(rename_all = "SCREAMING_SNAKE_CASE")]
+enum Protocol {
+    WireGuard,
+    #[jnix(rename = "ovpn")]
+    OpenVpn,
+    HTTPProxy,
+}
+
+#[test]
+fn ordinals_follow_declaration_order() {
+    with_env(|env| {
+        assert_eq!(<Ordinal as IntoJava>::JNI_SIGNATURE, "I");
+
+        for (ordinal, variant) in [Ordinal::First, Ordinal::Second, Ordinal::Third]
+            .iter()
+            .copied()
+            .enumerate()
+        {
+            let java_value = variant.into_java(env);
+
+            assert_eq!(java_value, ordinal as i32);
+            assert_eq!(Ordinal::from_java(env, java_value), variant);
+        }
+    });
+}
+
+#[test]
+#[should_panic(expected = "Can't convert unknown ordinal 3 into Ordinal Rust enum variant")]
+fn unknown_ordinals_are_rejected() {
+    with_env(|env| {
+        Ordinal::from_java(env, 3);
+    });
+}
+
+#[test]
+fn discriminants_are_kept() {
+    with_env(|env| {
+        assert_eq!(<Discriminant as IntoJava>::JNI_SIGNATURE, "I");
+
+        for (discriminant, variant) in [
+            (-1, Discriminant::Negative),
+            (i32::MAX, Discriminant::Large),
+        ] {
+            let java_value = variant.into_java(env);
+
+            assert_eq!(java_value, discriminant);
+            assert_eq!(Discriminant::from_java(env, java_value), variant);
+        }
+    });
+}
+
+#[test]
+#[should_panic(expected = "Can't convert unknown discriminant 0 into Discriminant Rust enum variant")]
+fn unknown_discriminants_are_rejected() {
+    with_env(|env| {
+        Discriminant::from_java(env, 0);
+    });
+}
+
+#[test]
+fn names_are_renamed() {
+    with_env(|env| {
+        assert_eq!(<Protocol as IntoJava>::JNI_SIGNATURE, "Ljava/lang/String;");
+
+        for (name, variant) in [
+            ("WIRE_GUARD", Protocol::WireGuard),
+            ("ovpn", Protocol::OpenVpn),
+            ("HTTP_PROXY", Protocol::HTTPProxy),
+        ] {
+            let java_value = variant.into_java(env);
+
+            assert_eq!(String::from_java(env, java_value.as_obj()), name);
+            assert_eq!(Protocol::from_java(env, java_value.as_obj()), variant);
+        }
+    });
+}
+
+#[test]
+#[should_panic(expected = "Can't convert unknown name OpenVpn into Protocol Rust enum variant")]
+fn unknown_names_are_rejected() {
+    with_env(|env| {
+        let java_value = "OpenVpn".into_java(env);
+
+        Protocol::from_java(env, java_value.as_obj());
+    });
+}
+
+#[test]
+fn discriminants_that_dont_fit_in_an_int_fail_the_build() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,23 @@
+mod common;
+
+use common::with_env;
+use jni::objects::JObject;
+use jnix::{FromJava, IntoJava};
+
+#[test]
+fn strings_are_converted_back_and_forth() {
+    with_env(|env| {
+        let string = "nul \0, surrogate pair \u{1f980}";
+        let java_string = string.into_java(env);
+
+        assert_eq!(String::from_java(env, java_string.as_obj()), string);
+    });
+}
+
+#[test]
+#[should_panic(expected = "Failed to convert from Java String")]
+fn null_strings_are_rejected() {
+    with_env(|env| {
+        String::from_java(env, JObject::null());
+    });
+}
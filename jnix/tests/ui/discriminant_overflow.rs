@@ -0,0 +1,11 @@
+use jnix::IntoJava;
+
+#[derive(Clone, Copy, IntoJava)]
+#[jnix(repr = "discriminant")]
+#[repr(i64)]
+enum Discriminant {
+    Small = 1,
+    Large = 0x8000_0000,
+}
+
+fn main() {}